@@ -0,0 +1,59 @@
+// Measures how fast `SnakeGame::tick` runs on boards of different sizes.
+//
+// The cost of a tick doesn't depend on the board's area, so the ticks per
+// second should stay about the same from the smallest to the largest board.
+//
+// Run with `cargo bench`.
+
+use std::{
+    hint::black_box,
+    time::Instant
+};
+
+use terminal_snake::{
+    Direction,
+    SnakeGame
+};
+
+const TICKS: u32 = 1_000_000;
+
+fn main() {
+    for size in [100, 1000, 2000] {
+        // borderless, the snake runs right and steps down a row every lap,
+        // sweeping the whole board and eating whatever is in its way
+        let mut game = SnakeGame::create(size, size, 1, 100, 100, 10, false, true);
+        game.set_seed(1);
+
+        let mut games = 1;
+
+        let now = Instant::now();
+
+        for tick in 0..TICKS {
+            let direction = if (tick as usize).is_multiple_of(size) {
+                Direction::Down
+            } else {
+                Direction::Right
+            };
+
+            game.tick(black_box(&direction));
+
+            if game.is_dead() {
+                game.clear();
+                games += 1;
+            }
+        }
+
+        let elapsed = now.elapsed();
+
+        println!(
+            "{:>4}x{:<4} {} ticks in {:>6.3}s, {:>10.0} ticks/s, {} games, final length {}",
+            size,
+            size,
+            TICKS,
+            elapsed.as_secs_f64(),
+            TICKS as f64 / elapsed.as_secs_f64(),
+            games,
+            game.snake_len()
+        );
+    }
+}
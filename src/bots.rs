@@ -0,0 +1,447 @@
+//! Reference bots built on [`SnakeGame`]'s state.
+//!
+//! They play the same game a player does, only through [`Controller`], so
+//! reading them is also a good way to learn the movement rules: a snake
+//! can't reverse into its neck and every snake cell and wall is deadly, even
+//! the tail that moves away on the same tick.
+
+use std::{
+    cmp::Reverse,
+    collections::{
+        BinaryHeap,
+        VecDeque
+    }
+};
+
+use crate::{
+    spawner::AppleKind,
+    Controller,
+    Direction,
+    SnakeGame,
+    SnakeGameCord,
+    Tile
+};
+
+/// Heads for the nearest apple worth eating, the most direct way that doesn't crash on
+/// the next tick.
+///
+/// It never looks further ahead than one step, so it happily steers into
+/// dead ends once the snake gets long.
+///
+/// ```
+/// use terminal_snake::{bots::Greedy, Config, Controller, SnakeGame};
+///
+/// let mut game = SnakeGame::from_config(&Config { seed: Some(7), ..Config::default() });
+/// let mut bot = Greedy;
+///
+/// for _ in 0..100 {
+///     let direction = bot.next_direction(&game, 0);
+///     game.tick(&direction);
+/// }
+///
+/// assert!(game.snake_len() > 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Greedy;
+
+impl Controller for Greedy {
+    fn next_direction(&mut self, game: &SnakeGame, player: usize) -> Direction {
+        let snake = &game.snakes()[player];
+        let head = snake.head();
+
+        let mut best: Option<(usize, Direction)> = None;
+
+        for direction in Direction::ALL {
+            if direction == snake.direction().opposite() {
+                continue;
+            }
+
+            let pos = match game.step(head, &direction) {
+                Some(pos) if is_safe(game, &pos) => pos,
+                _ => continue
+            };
+
+            let distance = targets(game).map(|apple| game.distance(&pos, &apple)).min().unwrap_or(0);
+
+            if best.as_ref().is_none_or(|(best, _)| distance < *best) {
+                best = Some((distance, direction));
+            }
+        }
+
+        match best {
+            Some((_, direction)) => direction,
+            // every way is blocked, the snake is done for anyway
+            None => snake.direction().clone()
+        }
+    }
+}
+
+/// Takes the shortest path to an apple, found with A*, but only if the
+/// snake could still reach its own tail after eating it.
+///
+/// Chasing its tail keeps the snake alive since the tail always moves out
+/// of the way. When no path is safe it takes the step that keeps the tail
+/// in reach and leaves the most room, and waits for a better chance.
+///
+/// ```
+/// use terminal_snake::{bots::AStar, simulation, Config};
+///
+/// let cfg = Config { width: 40, height: 15, ..Config::default() };
+/// let report = simulation::play(&cfg, 3, &mut AStar, 5_000);
+///
+/// assert!(report.length > 20);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AStar;
+
+impl Controller for AStar {
+    fn next_direction(&mut self, game: &SnakeGame, player: usize) -> Direction {
+        let snake = &game.snakes()[player];
+        let head = snake.head();
+        let blocked = blocked_cells(game);
+
+        if let Some(path) = a_star(game, &blocked, head) {
+            if tail_reachable_after(game, player, &blocked, &path) {
+                if let Some(direction) = direction_to(game, head, &path[0]) {
+                    return direction;
+                }
+            }
+        }
+
+        safest_direction(game, player, &blocked)
+    }
+}
+
+/// Follows a fixed cycle through every cell of the board, which makes the
+/// snake fill the whole board sooner or later.
+///
+/// Slow, since it ignores where the apples are, but guaranteed to win on a
+/// board without walls with an even width or height, in a one player game.
+/// Where no such cycle exists, or the next cell on it is taken, it plays
+/// like [`AStar`].
+///
+/// ```
+/// use terminal_snake::{bots::Hamiltonian, Config, Controller, SnakeGame, Victory};
+///
+/// let mut game = SnakeGame::from_config(&Config { width: 16, height: 4, seed: Some(1), ..Config::default() });
+/// let mut bot = Hamiltonian::default();
+///
+/// while ! game.is_over() {
+///     let direction = bot.next_direction(&game, 0);
+///     game.tick(&direction);
+/// }
+///
+/// assert_eq!(game.victory(), Some(Victory::BoardFilled));
+/// assert_eq!(game.snake_len(), 8 * 4);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Hamiltonian {
+    // the cell after and before each cell on the cycle, by cell index
+    next: Vec<usize>,
+    previous: Vec<usize>,
+    size: (usize, usize),
+    // the snake runs the cycle backwards, decided on the first tick
+    reversed: Option<bool>
+}
+
+impl Controller for Hamiltonian {
+    fn next_direction(&mut self, game: &SnakeGame, player: usize) -> Direction {
+        if self.size != (game.width(), game.height()) {
+            self.size = (game.width(), game.height());
+            self.next = hamiltonian_cycle(game.width(), game.height()).unwrap_or_default();
+            self.previous = vec![0; self.next.len()];
+
+            for (cell, next) in self.next.iter().enumerate() {
+                self.previous[*next] = cell;
+            }
+
+            self.reversed = None;
+        }
+
+        let has_walls = game.data().iter().any(|row| row.contains(&Tile::Wall));
+
+        if self.next.is_empty() || has_walls {
+            return AStar.next_direction(game, player);
+        }
+
+        let snake = &game.snakes()[player];
+        let head = snake.head();
+        let cell = head.y * game.width() + head.x;
+
+        // run the cycle the way that doesn't turn into the neck
+        let reversed = *self.reversed.get_or_insert_with(|| {
+            let neck = snake.body().iter().rev().nth(1);
+
+            neck.is_some_and(|neck| neck.y * game.width() + neck.x == self.next[cell])
+        });
+
+        let target = if reversed { self.previous[cell] } else { self.next[cell] };
+        let target = SnakeGameCord { x: target % game.width(), y: target / game.width() };
+
+        match direction_to(game, head, &target) {
+            Some(direction) if is_safe(game, &target) => direction,
+            _ => AStar.next_direction(game, player)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reversed = None;
+    }
+}
+
+/// Whether moving onto `pos` is safe for a single tick.
+pub fn is_safe(game: &SnakeGame, pos: &SnakeGameCord) -> bool {
+    matches!(game.data()[pos.y][pos.x], Tile::Empty | Tile::Apple(_))
+}
+
+/// The apples worth heading for, all but the [rotten](AppleKind::Rotten) ones.
+pub fn targets(game: &SnakeGame) -> impl Iterator<Item = SnakeGameCord> + '_ {
+    game.apples().filter(|apple| game.apple_kind(apple) != Some(AppleKind::Rotten))
+}
+
+/// The names [`bot`] knows.
+pub const NAMES: [&str; 3] = ["greedy", "astar", "hamiltonian"];
+
+/// The built-in bot called `name`.
+pub fn bot(name: &str) -> Option<Box<dyn Controller>> {
+    match name {
+        "greedy" => Some(Box::new(Greedy)),
+        "astar" => Some(Box::new(AStar)),
+        "hamiltonian" => Some(Box::new(Hamiltonian::default())),
+        _ => None
+    }
+}
+
+// the direction leading from `from` to the neighbouring cell `to`
+fn direction_to(game: &SnakeGame, from: &SnakeGameCord, to: &SnakeGameCord) -> Option<Direction> {
+    Direction::ALL.into_iter().find(|direction| game.step(from, direction).as_ref() == Some(to))
+}
+
+// the cells a snake can't move onto next tick, by cell index
+fn blocked_cells(game: &SnakeGame) -> Vec<bool> {
+    game.data().iter().flatten().map(|tile| matches!(tile, Tile::Wall | Tile::Snake(_))).collect()
+}
+
+fn neighbours<'a>(game: &'a SnakeGame, pos: &'a SnakeGameCord) -> impl Iterator<Item = SnakeGameCord> + 'a {
+    Direction::ALL.into_iter().filter_map(move |direction| game.step(pos, &direction))
+}
+
+// the shortest path from `start` to the nearest target, without `start`
+fn a_star(game: &SnakeGame, blocked: &[bool], start: &SnakeGameCord) -> Option<Vec<SnakeGameCord>> {
+    let apples: Vec<SnakeGameCord> = targets(game).collect();
+
+    if apples.is_empty() {
+        return None;
+    }
+
+    let width = game.width();
+    let index = |pos: &SnakeGameCord| pos.y * width + pos.x;
+    let heuristic = |pos: &SnakeGameCord| apples.iter().map(|apple| game.distance(pos, apple)).min().unwrap_or(0);
+
+    let mut came_from: Vec<Option<usize>> = vec![None; blocked.len()];
+    let mut cost = vec![usize::MAX; blocked.len()];
+    let mut open = BinaryHeap::new();
+
+    cost[index(start)] = 0;
+    open.push(Reverse((heuristic(start), 0, index(start))));
+
+    while let Some(Reverse((_, steps, cell))) = open.pop() {
+        if steps > cost[cell] {
+            continue;
+        }
+
+        let pos = SnakeGameCord { x: cell % width, y: cell / width };
+
+        if apples.contains(&pos) {
+            let mut path = vec![pos];
+            let mut cell = cell;
+
+            while let Some(previous) = came_from[cell] {
+                if previous == index(start) {
+                    break;
+                }
+
+                path.push(SnakeGameCord { x: previous % width, y: previous / width });
+                cell = previous;
+            }
+
+            path.reverse();
+
+            return Some(path);
+        }
+
+        for next in neighbours(game, &pos) {
+            let next_cell = index(&next);
+
+            if blocked[next_cell] || steps + 1 >= cost[next_cell] {
+                continue;
+            }
+
+            cost[next_cell] = steps + 1;
+            came_from[next_cell] = Some(cell);
+            open.push(Reverse((steps + 1 + heuristic(&next), steps + 1, next_cell)));
+        }
+    }
+
+    None
+}
+
+// whether the snake of `player` could still reach its tail after following `path`
+//
+// the body after the path is its old body with the path appended, one cell
+// longer than before for the apple at the end
+fn tail_reachable_after(game: &SnakeGame, player: usize, blocked: &[bool], path: &[SnakeGameCord]) -> bool {
+    let snake = &game.snakes()[player];
+    let width = game.width();
+
+    let mut body: VecDeque<SnakeGameCord> = snake.body().clone();
+    body.extend(path.iter().cloned());
+
+    let length = snake.body().len() + 1;
+
+    while body.len() > length {
+        body.pop_front();
+    }
+
+    let mut blocked = blocked.to_vec();
+
+    for cell in snake.body() {
+        blocked[cell.y * width + cell.x] = false;
+    }
+
+    for cell in &body {
+        blocked[cell.y * width + cell.x] = true;
+    }
+
+    match (body.back(), body.front()) {
+        (Some(head), Some(tail)) => flood(game, &blocked, head, Some(tail)).1,
+        _ => false
+    }
+}
+
+// the number of free cells reachable from `from` and whether `target` is
+// reachable, `target` counts even if it is blocked itself
+fn flood(game: &SnakeGame, blocked: &[bool], from: &SnakeGameCord, target: Option<&SnakeGameCord>) -> (usize, bool) {
+    let width = game.width();
+
+    let mut seen = vec![false; blocked.len()];
+    let mut queue = VecDeque::from([from.clone()]);
+    let mut area = 0;
+    let mut found = false;
+
+    seen[from.y * width + from.x] = true;
+
+    while let Some(pos) = queue.pop_front() {
+        for next in neighbours(game, &pos) {
+            let cell = next.y * width + next.x;
+
+            if target == Some(&next) {
+                found = true;
+            }
+
+            if seen[cell] || blocked[cell] {
+                continue;
+            }
+
+            seen[cell] = true;
+            area += 1;
+            queue.push_back(next);
+        }
+    }
+
+    (area, found)
+}
+
+// the step that keeps the tail in reach with the most room around it, or
+// the most room if the tail is out of reach anyway
+fn safest_direction(game: &SnakeGame, player: usize, blocked: &[bool]) -> Direction {
+    let snake = &game.snakes()[player];
+    let head = snake.head();
+    let width = game.width();
+
+    let mut best: Option<((bool, usize, usize), Direction)> = None;
+
+    for direction in Direction::ALL {
+        let pos = match game.step(head, &direction) {
+            Some(pos) if ! blocked[pos.y * width + pos.x] => pos,
+            _ => continue
+        };
+
+        let mut after = blocked.to_vec();
+        after[pos.y * width + pos.x] = true;
+
+        let tail = snake.body().front().unwrap_or(head);
+
+        let (area, tail_reachable) = flood(game, &after, &pos, Some(tail));
+
+        // further from the tail stalls longer while it makes room
+        let score = (tail_reachable, area, game.distance(&pos, tail));
+
+        if best.as_ref().is_none_or(|(best, _)| score > *best) {
+            best = Some((score, direction));
+        }
+    }
+
+    match best {
+        Some((_, direction)) => direction,
+        None => snake.direction().clone()
+    }
+}
+
+// the successor of every cell on a cycle through all cells of a `width` x
+// `height` board, `None` if there is none
+//
+// with an even height the cycle runs right along the top row, snakes
+// through columns 1 and up row by row and comes back up column 0, with an
+// odd height the board is handled turned on its side
+fn hamiltonian_cycle(width: usize, height: usize) -> Option<Vec<usize>> {
+    if width < 2 || height < 2 {
+        return None;
+    }
+
+    if ! height.is_multiple_of(2) {
+        if ! width.is_multiple_of(2) {
+            return None;
+        }
+
+        // the cycle of the transposed board, translated back
+        let transposed = hamiltonian_cycle(height, width)?;
+
+        let mut next = vec![0; width * height];
+
+        for (cell, successor) in transposed.iter().enumerate() {
+            let (x, y) = (cell / height, cell % height);
+            let (next_x, next_y) = (successor / height, successor % height);
+
+            next[y * width + x] = next_y * width + next_x;
+        }
+
+        return Some(next);
+    }
+
+    let mut order = vec![];
+
+    for y in 0..height {
+        if y % 2 == 0 {
+            for x in 1..width {
+                order.push(y * width + x);
+            }
+        } else {
+            for x in (1..width).rev() {
+                order.push(y * width + x);
+            }
+        }
+    }
+
+    for y in (0..height).rev() {
+        order.push(y * width);
+    }
+
+    let mut next = vec![0; width * height];
+
+    for (i, cell) in order.iter().enumerate() {
+        next[*cell] = order[(i + 1) % order.len()];
+    }
+
+    Some(next)
+}
@@ -0,0 +1,207 @@
+use crossterm::style::Color;
+
+use terminal_snake::{
+    config::Renderer,
+    SnakeGame,
+    SnakeGameCord,
+    Tile
+};
+
+use crate::{
+    glyphs,
+    render::Screen,
+    PLAYER_COLORS
+};
+
+// The part of a board that's on the screen and where it's drawn.
+//
+// A board that fits is shown whole in the middle of the screen. A larger one
+// is cropped to the cells around a focus cell, usually the snake's head, so
+// the view scrolls along with it, and keeps a row free above and below for
+// the hud and status lines.
+#[derive(Debug, Clone, Copy)]
+pub struct Viewport {
+    // the screen position of the top left corner of the border
+    pub left: u16,
+    pub top: u16,
+    // the first visible cell
+    pub x: usize,
+    pub y: usize,
+    // the number of visible cells
+    pub width: usize,
+    pub height: usize,
+    // the number of columns and rows inside the border
+    pub columns: u16,
+    pub rows: u16,
+    board_width: usize,
+    board_height: usize
+}
+
+impl Viewport {
+    // the view of a `board_width` x `board_height` board drawn by `renderer`
+    // centered on `focus` as far as the edges of the board allow
+    pub fn new(screen: &Screen, board_width: usize, board_height: usize, focus: &SnakeGameCord, renderer: Renderer) -> Viewport {
+        let (s_width, s_height) = screen.size();
+        let (block_width, block_height, columns) = glyphs::block(renderer);
+
+        let (left, x, width) = layout_axis(s_width as usize, block_width, columns, board_width, focus.x, 0);
+        let (top, y, height) = layout_axis(s_height as usize, block_height, 1, board_height, focus.y, 1);
+
+        Viewport {
+            left,
+            top,
+            x,
+            y,
+            width,
+            height,
+            columns: (width.div_ceil(block_width) * columns) as u16,
+            rows: height.div_ceil(block_height) as u16,
+            board_width,
+            board_height
+        }
+    }
+
+    // whether part of the board is cut off
+    pub fn is_cropped(&self) -> bool {
+        self.width < self.board_width || self.height < self.board_height
+    }
+
+    // whether the board goes on past the left, right, top and bottom border
+    pub fn cut_edges(&self) -> (bool, bool, bool, bool) {
+        (
+            self.x > 0,
+            self.x + self.width < self.board_width,
+            self.y > 0,
+            self.y + self.height < self.board_height
+        )
+    }
+
+    // the row of the bottom border
+    pub fn bottom(&self) -> u16 {
+        self.top + self.rows + 1
+    }
+
+    // the column of the right border
+    pub fn right(&self) -> u16 {
+        self.left + self.columns + 1
+    }
+}
+
+// lays out one direction of the board on `space` screen positions, the
+// cells are drawn in blocks of `block` that take `size` of them and the
+// border one on each side, `reserved` more on each side stay free when the
+// board doesn't fit. Returns the position of the border, the first visible
+// cell and the number of visible cells.
+fn layout_axis(space: usize, block: usize, size: usize, cells: usize, focus: usize, reserved: usize) -> (u16, usize, usize) {
+    let blocks = cells.div_ceil(block);
+
+    if blocks * size + 2 <= space {
+        return (((space - blocks * size - 2) / 2) as u16, 0, cells);
+    }
+
+    let visible = (space.saturating_sub(2 + 2 * reserved) / size).clamp(1, blocks.max(1));
+    let first = (focus / block).saturating_sub(visible / 2).min(blocks - visible) * block;
+
+    ((space.saturating_sub(visible * size + 2) / 2) as u16, first, (visible * block).min(cells - first))
+}
+
+// the cell the camera follows, the head of the first snake still alive
+pub fn focus(game: &SnakeGame) -> SnakeGameCord {
+    let snake = game.snakes().iter().find(|snake| ! snake.is_dead()).unwrap_or(&game.snakes()[0]);
+
+    snake.head().clone()
+}
+
+// the largest size of the minimap in characters
+const MINIMAP_WIDTH: usize = 24;
+const MINIMAP_HEIGHT: usize = 8;
+
+// draws the whole board shrunk down into the top right corner of a cropped
+// `viewport`, with the visible part highlighted. Every character stands for
+// a block of cells and shows the most important thing in it: a snake, an
+// apple or a wall.
+pub fn draw_minimap(screen: &mut Screen, game: &SnakeGame, viewport: &Viewport) {
+    if ! viewport.is_cropped() {
+        return;
+    }
+
+    let (board_width, board_height) = (game.width(), game.height());
+
+    // the minimap sits inside the border and takes at most half of the view
+    // in each direction
+    let max_width = MINIMAP_WIDTH.min((viewport.columns as usize).saturating_sub(2)).min(board_width);
+    let max_height = MINIMAP_HEIGHT.min(viewport.rows as usize / 2).min(board_height);
+
+    if max_width < 4 || max_height < 2 {
+        return;
+    }
+
+    let block_width = board_width.div_ceil(max_width);
+    let block_height = board_height.div_ceil(max_height);
+
+    let width = board_width.div_ceil(block_width);
+    let height = board_height.div_ceil(block_height);
+
+    let left = viewport.right() - width as u16 - 2;
+    let top = viewport.top + 1;
+
+    let frame = "+".repeat(width + 2);
+
+    screen.print(left, top, &frame, Color::DarkGrey);
+    screen.print(left, top + height as u16 + 1, &frame, Color::DarkGrey);
+
+    // only the cells that aren't empty are looked at, snakes, apples and
+    // walls, not the whole board
+    let mut blocks = vec![Tile::Empty; width * height];
+
+    let cells = game.walls().iter().cloned()
+        .chain(game.apples())
+        .chain(game.snakes().iter().flat_map(|snake| snake.body().iter().cloned()));
+
+    for pos in cells {
+        let tile = game.data()[pos.y][pos.x];
+        let block = &mut blocks[pos.y / block_height * width + pos.x / block_width];
+
+        if importance(&tile) > importance(block) {
+            *block = tile;
+        }
+    }
+
+    for my in 0..height {
+        let y = top + 1 + my as u16;
+
+        screen.print(left, y, "+", Color::DarkGrey);
+        screen.print(left + width as u16 + 1, y, "+", Color::DarkGrey);
+
+        for mx in 0..width {
+            let xs = mx * block_width..((mx + 1) * block_width).min(board_width);
+            let ys = my * block_height..((my + 1) * block_height).min(board_height);
+
+            let visible = xs.start < viewport.x + viewport.width && xs.end > viewport.x
+                && ys.start < viewport.y + viewport.height && ys.end > viewport.y;
+
+            let shown = blocks[my * width + mx];
+
+            let (ch, fg) = match shown {
+                Tile::Snake(player) => ('*', PLAYER_COLORS[player % PLAYER_COLORS.len()]),
+                Tile::Apple(_) => ('o', Color::Red),
+                Tile::Wall => ('#', Color::Grey),
+                Tile::Empty => (' ', Color::White)
+            };
+
+            let bg = if visible { Color::DarkGrey } else { Color::Black };
+
+            screen.print_colored(left + 1 + mx as u16, y, &ch.to_string(), fg, bg);
+        }
+    }
+}
+
+// which tile a minimap block or braille character shows when it has several
+pub fn importance(tile: &Tile) -> u8 {
+    match tile {
+        Tile::Empty => 0,
+        Tile::Wall => 1,
+        Tile::Apple(_) => 2,
+        Tile::Snake(_) => 3
+    }
+}
@@ -0,0 +1,78 @@
+use rand::Rng;
+
+const ABSENT: usize = usize::MAX;
+
+/// A set of board cells, addressed by their index `y * width + x`.
+///
+/// Inserting, removing and picking a uniformly random member are all O(1),
+/// which lets [`crate::SnakeGame`] keep track of free cells and apples
+/// without scanning the board.
+#[derive(Clone)]
+pub(crate) struct CellSet {
+    cells: Vec<usize>,
+    // position of every cell in `cells`, `ABSENT` if it isn't a member
+    positions: Vec<usize>
+}
+
+impl CellSet {
+    /// An empty set for a board of `size` cells.
+    pub(crate) fn empty(size: usize) -> CellSet {
+        CellSet {
+            cells: vec![],
+            positions: vec![ABSENT; size]
+        }
+    }
+
+    /// A set containing every cell of a board of `size` cells.
+    pub(crate) fn full(size: usize) -> CellSet {
+        CellSet {
+            cells: (0..size).collect(),
+            positions: (0..size).collect()
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub(crate) fn contains(&self, cell: usize) -> bool {
+        self.positions[cell] != ABSENT
+    }
+
+    pub(crate) fn insert(&mut self, cell: usize) {
+        if self.contains(cell) {
+            return;
+        }
+
+        self.positions[cell] = self.cells.len();
+        self.cells.push(cell);
+    }
+
+    pub(crate) fn remove(&mut self, cell: usize) {
+        let pos = self.positions[cell];
+
+        if pos == ABSENT {
+            return;
+        }
+
+        self.cells.swap_remove(pos);
+        self.positions[cell] = ABSENT;
+
+        if let Some(moved) = self.cells.get(pos) {
+            self.positions[*moved] = pos;
+        }
+    }
+
+    /// A uniformly random member, `None` if the set is empty.
+    pub(crate) fn choose<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        if self.cells.is_empty() {
+            return None;
+        }
+
+        Some(self.cells[rng.gen_range(0..self.cells.len())])
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cells.iter().copied()
+    }
+}
@@ -0,0 +1,259 @@
+use std::path::PathBuf;
+
+use clap::{
+    Args,
+    Parser,
+    Subcommand,
+    ValueEnum
+};
+
+use terminal_snake::{
+    external::FailurePolicy,
+    config::Renderer,
+    spawner::ApplePolicy,
+    Config,
+    Layout
+};
+
+/// Play snake in the terminal.
+///
+/// Without a command the main menu opens. The settings flags apply to this
+/// run only, the settings file stays as it is.
+#[derive(Parser)]
+#[command(name = "terminal-snake", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub overrides: Overrides,
+
+    /// Don't store changes made in the settings menu, for kiosks and shared machines
+    #[arg(long, global = true)]
+    pub no_save: bool
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Start a game right away
+    Play {
+        /// Play this level file instead of the open arena
+        #[arg(long)]
+        level: Option<PathBuf>,
+
+        /// The number of players, WASD steers the first and the arrows the second
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        players: u8,
+
+        /// Play against a built-in bot (greedy, astar, hamiltonian) or a bot
+        /// program, split at spaces. It steers the last snake
+        #[arg(long, conflicts_with = "players")]
+        opponent: Option<String>
+    },
+    /// Watch a bot play game after game, until a key is pressed
+    Demo {
+        /// A built-in bot (greedy, astar, hamiltonian) or the command line of
+        /// a bot program, split at spaces
+        #[arg(long, default_value = "astar")]
+        bot: String
+    },
+    /// Watch a saved replay, or pick one of the recent ones
+    Replay {
+        file: Option<PathBuf>
+    },
+    /// Print the high score table of the current settings
+    Scores {
+        /// Print the tables of all settings
+        #[arg(long)]
+        all: bool
+    },
+    /// Print the levels in the levels directory
+    Levels,
+    /// Play games with a bot without a terminal and print statistics about them
+    Simulate {
+        /// A built-in bot (greedy, astar, hamiltonian) or the command line of
+        /// a bot program, split at spaces. Repeat it to let bots play against each other
+        #[arg(long = "bot", default_value = "greedy")]
+        bots: Vec<String>,
+
+        /// Milliseconds a bot program gets to answer
+        #[arg(long, default_value_t = 100)]
+        bot_timeout: u64,
+
+        /// What happens to the snake of a bot program that doesn't answer in time or answers nonsense
+        #[arg(long, value_enum, default_value_t = OnBotFailure::Keep)]
+        on_bot_failure: OnBotFailure,
+
+        /// How many games to play, with `--seed` game i uses seed + i
+        #[arg(long, default_value_t = 100)]
+        games: u64,
+
+        /// Stop a game after this many ticks, the snake counts as survived
+        #[arg(long, default_value_t = 100_000)]
+        max_ticks: u64,
+
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format
+    },
+    /// Print where the settings are stored and what they are
+    Config {
+        /// Store the settings flags given on this command line permanently
+        #[arg(long)]
+        save: bool
+    }
+}
+
+/// How `simulate` prints its statistics.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Json,
+    Csv
+}
+
+/// See [`FailurePolicy`].
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OnBotFailure {
+    /// Keep heading the way the snake went
+    Keep,
+    /// Give up the game
+    Forfeit
+}
+
+impl OnBotFailure {
+    pub fn policy(&self) -> FailurePolicy {
+        match self {
+            OnBotFailure::Keep => FailurePolicy::KeepDirection,
+            OnBotFailure::Forfeit => FailurePolicy::Forfeit
+        }
+    }
+}
+
+/// Settings that replace the stored ones for a single run.
+#[derive(Args)]
+pub struct Overrides {
+    /// The width of the arena in terminal columns, two per cell
+    #[arg(long, global = true, value_parser = clap::value_parser!(u16).range(4..))]
+    pub width: Option<u16>,
+
+    /// The height of the arena in cells
+    #[arg(long, global = true, value_parser = clap::value_parser!(u16).range(2..))]
+    pub height: Option<u16>,
+
+    /// The fewest apples on the board at once
+    #[arg(long, global = true)]
+    pub min_apples: Option<u32>,
+
+    /// The most apples on the board at once
+    #[arg(long, global = true)]
+    pub max_apples: Option<u32>,
+
+    /// Ticks between two apple spawns
+    #[arg(long, global = true)]
+    pub apple_interval: Option<u32>,
+
+    /// When apples appear: interval, random-interval (between none and twice
+    /// the interval) or keep-max (always the most apples)
+    #[arg(long, global = true)]
+    pub apple_policy: Option<ApplePolicy>,
+
+    /// The fewest steps between a new apple and a snake's head
+    #[arg(long, global = true)]
+    pub apple_distance: Option<u32>,
+
+    /// How many cells the snake grows per apple
+    #[arg(long, global = true)]
+    pub grow_rate: Option<u32>,
+
+    /// Steps per second
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..))]
+    pub speed: Option<u32>,
+
+    /// Lose length instead of dying, `--immortal=false` turns it off
+    #[arg(long, global = true, num_args = 0..=1, default_missing_value = "true")]
+    pub immortal: Option<bool>,
+
+    /// Wrap around the edges, `--borderless=false` turns it off
+    #[arg(long, global = true, num_args = 0..=1, default_missing_value = "true")]
+    pub borderless: Option<bool>,
+
+    /// open, cross, pillars or rooms
+    #[arg(long, global = true)]
+    pub layout: Option<Layout>,
+
+    /// Win once the snake is this long, 0 turns it off
+    #[arg(long, global = true)]
+    pub win_length: Option<u32>,
+
+    /// Win by surviving this many seconds, 0 turns it off
+    #[arg(long, global = true)]
+    pub win_seconds: Option<u32>,
+
+    /// Seed for apple spawning, the same seed gives the same apples
+    #[arg(long, global = true)]
+    pub seed: Option<u64>,
+
+    /// How the board is drawn: blocks, half-blocks (two rows per character)
+    /// or braille (2x4 cells per character)
+    #[arg(long, global = true)]
+    pub renderer: Option<Renderer>
+}
+
+impl Overrides {
+    // `cfg` with every given flag applied
+    pub fn apply(&self, cfg: &Config) -> Config {
+        let mut cfg = cfg.clone();
+
+        if let Some(width) = self.width {
+            cfg.width = width as usize;
+        }
+        if let Some(height) = self.height {
+            cfg.height = height as usize;
+        }
+        if let Some(min_apples) = self.min_apples {
+            cfg.min_apple_count = min_apples;
+            cfg.max_apple_count = cfg.max_apple_count.max(min_apples);
+        }
+        if let Some(max_apples) = self.max_apples {
+            cfg.max_apple_count = max_apples;
+            cfg.min_apple_count = cfg.min_apple_count.min(max_apples);
+        }
+        if let Some(apple_interval) = self.apple_interval {
+            cfg.ticks_between_apple_spawn = apple_interval;
+        }
+        if let Some(apple_policy) = self.apple_policy {
+            cfg.apple_policy = apple_policy;
+        }
+        if let Some(apple_distance) = self.apple_distance {
+            cfg.min_apple_distance = apple_distance;
+        }
+        if let Some(grow_rate) = self.grow_rate {
+            cfg.game_grow_rate = grow_rate;
+        }
+        if let Some(speed) = self.speed {
+            cfg.steps_per_second = speed;
+        }
+        if let Some(immortal) = self.immortal {
+            cfg.easy = immortal;
+        }
+        if let Some(borderless) = self.borderless {
+            cfg.borderless = borderless;
+        }
+        if let Some(layout) = self.layout {
+            cfg.layout = layout;
+        }
+        if let Some(win_length) = self.win_length {
+            cfg.win_length = Some(win_length).filter(|length| *length > 0);
+        }
+        if let Some(win_seconds) = self.win_seconds {
+            cfg.win_seconds = Some(win_seconds).filter(|seconds| *seconds > 0);
+        }
+        if self.seed.is_some() {
+            cfg.seed = self.seed;
+        }
+        if let Some(renderer) = self.renderer {
+            cfg.renderer = renderer;
+        }
+
+        cfg
+    }
+}
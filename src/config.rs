@@ -0,0 +1,203 @@
+use std::str::FromStr;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    spawner::{
+        AppleKind,
+        ApplePolicy,
+        AppleWeights
+    },
+    Layout
+};
+
+/// The user's settings, persisted between runs by the `terminal-snake` binary.
+///
+/// Fields missing from a stored config fall back to their default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// The width of the arena in terminal columns, every cell is two columns wide.
+    pub width: usize,
+    /// The height of the arena in cells.
+    pub height: usize,
+    pub min_apple_count: u32,
+    pub max_apple_count: u32,
+    pub ticks_between_apple_spawn: u32,
+    /// When apples appear, see [`AppleSpawner`](crate::spawner::AppleSpawner).
+    pub apple_policy: ApplePolicy,
+    /// The fewest steps between a new apple and a snake's head.
+    pub min_apple_distance: u32,
+    /// How many cells the snake grows per apple eaten.
+    pub game_grow_rate: u32,
+    pub steps_per_second: u32,
+    /// IMMORTAL, the snake loses length instead of dying.
+    pub easy: bool,
+    /// The snake wraps around the edges of the arena.
+    pub borderless: bool,
+    /// The walls inside the arena.
+    pub layout: Layout,
+    /// The number of snakes, every player controls one.
+    pub players: usize,
+    /// Seconds counted down before a paused game goes on, 0 goes on at once.
+    pub resume_countdown: u32,
+    /// Games pause when the terminal loses focus, if it reports that.
+    pub pause_on_focus_loss: bool,
+    /// Games pause when the terminal is resized.
+    pub pause_on_resize: bool,
+    /// A board larger than the terminal shows a minimap of the whole board.
+    pub minimap: bool,
+    /// How the terminal front-end draws the board.
+    pub renderer: Renderer,
+    /// The game is won once a snake is this long, besides by filling the board.
+    pub win_length: Option<u32>,
+    /// The game is won by surviving this many seconds of game time, sped up
+    /// ticks count for less, see [`SnakeGame::set_goals`](crate::SnakeGame::set_goals).
+    pub win_seconds: Option<u32>,
+    /// Seed for apple spawning, every game is random when unset.
+    pub seed: Option<u64>,
+    /// How often each kind of apple appears, last since it's a table in the settings file.
+    pub apple_weights: AppleWeights
+}
+
+impl std::default::Default for Config {
+    fn default() -> Self {
+        Config {
+            width: 80,
+            height: 30,
+            min_apple_count: 1,
+            max_apple_count: 1,
+            ticks_between_apple_spawn: 100,
+            apple_policy: ApplePolicy::Interval,
+            min_apple_distance: 0,
+            game_grow_rate: 1,
+            steps_per_second: 10,
+            easy: false,
+            borderless: false,
+            layout: Layout::Open,
+            players: 1,
+            resume_countdown: 3,
+            pause_on_focus_loss: true,
+            pause_on_resize: false,
+            minimap: true,
+            renderer: Renderer::Blocks,
+            win_length: None,
+            win_seconds: None,
+            seed: None,
+            apple_weights: AppleWeights::default()
+        }
+    }
+}
+
+impl Config {
+    /// A hash of the settings that change how hard a game is, games with the
+    /// same hash compete in the same high score table.
+    ///
+    /// Covers the size, the apple settings, the speed, `easy`, `borderless`,
+    /// the layout and the win conditions.
+    ///
+    /// Settings added later only count when they aren't at their default, so
+    /// tables from before they existed keep their hash.
+    /// The hash is FNV-1a over the fields, so it's stable between builds.
+    ///
+    /// ```
+    /// use terminal_snake::{Config, Layout};
+    ///
+    /// let cfg = Config::default();
+    ///
+    /// assert_eq!(cfg.mode_hash(), Config { seed: Some(3), ..Config::default() }.mode_hash());
+    /// assert_ne!(cfg.mode_hash(), Config { easy: true, ..Config::default() }.mode_hash());
+    /// assert_ne!(cfg.mode_hash(), Config { layout: Layout::Cross, ..Config::default() }.mode_hash());
+    /// ```
+    pub fn mode_hash(&self) -> u64 {
+        let mut fields = vec![
+            self.width as u64,
+            self.height as u64,
+            self.min_apple_count as u64,
+            self.max_apple_count as u64,
+            self.ticks_between_apple_spawn as u64,
+            self.game_grow_rate as u64,
+            self.steps_per_second as u64,
+            self.easy as u64,
+            self.borderless as u64
+        ];
+
+        if let Some(win_length) = self.win_length {
+            fields.extend([1, win_length as u64]);
+        }
+        if let Some(win_seconds) = self.win_seconds {
+            fields.extend([2, win_seconds as u64]);
+        }
+        if self.apple_policy != ApplePolicy::Interval {
+            fields.extend([3, self.apple_policy as u64]);
+        }
+        if self.min_apple_distance != 0 {
+            fields.extend([4, self.min_apple_distance as u64]);
+        }
+        if self.apple_weights != AppleWeights::default() {
+            fields.push(5);
+            fields.extend(AppleKind::ALL.map(|kind| self.apple_weights.get(kind) as u64));
+        }
+        if self.layout != Layout::Open {
+            fields.extend([6, self.layout as u64]);
+        }
+
+        let mut hash: u64 = 0xcbf29ce484222325;
+
+        for byte in fields.iter().flat_map(|field| field.to_le_bytes()) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+
+        hash
+    }
+}
+
+/// How the board is drawn, the game plays the same with all of them.
+///
+/// The packed renderers need a terminal with unicode glyphs, the front-end
+/// falls back to [`Renderer::Blocks`] without one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Renderer {
+    /// Every cell is two characters wide, like `[]` and `()`.
+    #[default]
+    Blocks,
+    /// Every character shows two cells on top of each other with half block
+    /// glyphs, one column and half a row per cell.
+    HalfBlocks,
+    /// Every character shows 2x4 cells as braille dots, for very large boards.
+    Braille
+}
+
+impl Renderer {
+    pub const ALL: [Renderer; 3] = [Renderer::Blocks, Renderer::HalfBlocks, Renderer::Braille];
+
+    /// The name shown in the settings menu.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Renderer::Blocks => "BLOCKS",
+            Renderer::HalfBlocks => "HALF BLOCKS",
+            Renderer::Braille => "BRAILLE"
+        }
+    }
+
+    /// The renderer after this one, wrapping around.
+    pub fn next(&self) -> Renderer {
+        let i = Renderer::ALL.iter().position(|renderer| renderer == self).unwrap_or(0);
+
+        Renderer::ALL[(i + 1) % Renderer::ALL.len()]
+    }
+}
+
+/// Parses [`Renderer::name`], ignoring case, with `-` or `_` for spaces.
+impl FromStr for Renderer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Renderer, String> {
+        let name = s.replace(['-', '_'], " ");
+
+        Renderer::ALL.into_iter()
+            .find(|renderer| renderer.name().eq_ignore_ascii_case(&name))
+            .ok_or_else(|| format!("unknown renderer \"{}\", expected blocks, half-blocks or braille", s))
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{
+    Direction,
+    SnakeGame
+};
+
+/// Decides where a snake heads, one decision per tick.
+///
+/// A front-end asks the controller of every snake for its direction and
+/// passes them to [`SnakeGame::tick_all`]. A player at a keyboard is an
+/// [`InputQueue`], a bot looks at the board and picks a direction itself.
+pub trait Controller {
+    /// The direction snake `player` should head into on the next tick of `game`.
+    fn next_direction(&mut self, game: &SnakeGame, player: usize) -> Direction;
+
+    /// Hands over a direction the player asked for, for example with a key press.
+    ///
+    /// Controllers that don't take input ignore it.
+    fn input(&mut self, _direction: Direction) {}
+
+    /// Whether the controller gave up, checked after every
+    /// [`Controller::next_direction`]. The front-end then calls
+    /// [`SnakeGame::forfeit`] for its snake.
+    fn forfeited(&self) -> bool {
+        false
+    }
+
+    /// How many times since the last [`Controller::reset`] the controller
+    /// couldn't come up with a direction and the snake kept its own.
+    fn failures(&self) -> u64 {
+        0
+    }
+
+    /// Forgets everything about the last game, called before a new one starts.
+    fn reset(&mut self) {}
+}
+
+/// The direction inputs of a player, applied one per tick.
+///
+/// Inputs that wouldn't turn the snake are skipped, so pressing two keys
+/// quickly makes two turns on two ticks. At most three inputs wait for the
+/// ticks after that.
+///
+/// ```
+/// use terminal_snake::{Config, Controller, Direction, InputQueue, SnakeGame};
+///
+/// let mut game = SnakeGame::from_config(&Config::default());
+/// let mut keys = InputQueue::new();
+///
+/// // the snake starts heading right, so right doesn't turn it
+/// keys.input(Direction::Right);
+/// keys.input(Direction::Up);
+/// keys.input(Direction::Left);
+///
+/// let direction = keys.next_direction(&game, 0);
+/// assert_eq!(direction, Direction::Up);
+/// game.tick(&direction);
+///
+/// let direction = keys.next_direction(&game, 0);
+/// assert_eq!(direction, Direction::Left);
+/// game.tick(&direction);
+///
+/// assert_eq!(keys.next_direction(&game, 0), Direction::Left);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InputQueue {
+    queue: Vec<Direction>
+}
+
+impl InputQueue {
+    pub fn new() -> InputQueue {
+        InputQueue::default()
+    }
+}
+
+impl Controller for InputQueue {
+    fn next_direction(&mut self, game: &SnakeGame, player: usize) -> Direction {
+        let mut direction = game.snakes()[player].direction().clone();
+
+        let mut iter = self.queue.iter();
+
+        'select_next_relevant_queue_result: loop {
+            match iter.next() {
+                Some(dir) => if dir.kind() != direction.kind() {
+                    direction = dir.clone();
+                    break 'select_next_relevant_queue_result;
+                },
+                None => {
+                    break 'select_next_relevant_queue_result;
+                }
+            }
+        }
+
+        let mut new_queue = vec![];
+
+        'collect_remaining: loop {
+            match iter.next() {
+                Some(dir) => {
+                    new_queue.push(dir.clone());
+
+                    if new_queue.len() >= 3 {
+                        break 'collect_remaining;
+                    }
+                },
+                None => {
+                    break 'collect_remaining;
+                }
+            }
+        }
+
+        self.queue = new_queue;
+
+        direction
+    }
+
+    fn input(&mut self, direction: Direction) {
+        self.queue.push(direction);
+    }
+
+    fn reset(&mut self) {
+        self.queue.clear();
+    }
+}
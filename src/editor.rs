@@ -0,0 +1,152 @@
+use std::{
+    io::Stdout,
+    path::Path
+};
+
+use crossterm::{
+    event::{
+        Event,
+        KeyCode
+    },
+    style::Color,
+    Result
+};
+
+use terminal_snake::{
+    Direction,
+    Level,
+    SnakeGameCord
+};
+
+use crate::{
+    draw_board,
+    menue,
+    render::Screen,
+    terminal::read
+};
+
+const HELP: &str = "[ARROWS] move [SPACE] wall [O] apple spawn [S] start/turn [U] undo [ENTER] save [ESC] back";
+
+// lets the player paint walls, apple spawn points and the start of `level`,
+// ENTER saves it to `path`
+pub fn edit_level(stdout: &mut Stdout, level: &mut Level, path: &Path) -> Result<()> {
+    let mut cursor = level.start.clone();
+    let mut undo: Vec<Level> = vec![];
+    let mut saved = path.exists();
+    let mut status = String::from(HELP);
+
+    let mut screen = Screen::new();
+
+    'editing: loop {
+        screen.begin()?;
+
+        // the view follows the cursor across levels larger than the terminal
+        draw_board(&mut screen, level.width, level.height, level.settings.borderless.unwrap_or(false), &cursor.clone(), |x, y| {
+            let cord = SnakeGameCord { x, y };
+
+            let bg = if cord == cursor {
+                Color::DarkGrey
+            } else {
+                Color::Black
+            };
+
+            if cord == level.start {
+                let arrow = match level.start_direction {
+                    Direction::Up => "^^",
+                    Direction::Down => "vv",
+                    Direction::Left => "<<",
+                    Direction::Right => ">>"
+                };
+
+                Some((arrow, Color::Green, bg))
+            } else if level.walls.contains(&cord) {
+                Some(("##", Color::Grey, bg))
+            } else if level.apple_spawns.contains(&cord) {
+                Some(("()", Color::DarkRed, bg))
+            } else if cord == cursor {
+                Some(("  ", Color::White, bg))
+            } else {
+                None
+            }
+        })?;
+
+        let (s_width, s_height) = screen.size();
+
+        let mut line = format!("{}{}", if saved { "" } else { "* " }, status);
+        line.truncate(s_width as usize);
+
+        screen.print(0, s_height.saturating_sub(1), &line, Color::White);
+        screen.present(stdout)?;
+
+        let before = level.clone();
+        let mut undoing = false;
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Up => cursor.y = cursor.y.saturating_sub(1),
+                KeyCode::Down if cursor.y + 1 < level.height => cursor.y += 1,
+                KeyCode::Left => cursor.x = cursor.x.saturating_sub(1),
+                KeyCode::Right if cursor.x + 1 < level.width => cursor.x += 1,
+                KeyCode::Char(' ') => level.toggle_wall(&cursor),
+                KeyCode::Char(c) => match c.to_ascii_uppercase() {
+                    'O' => level.toggle_apple_spawn(&cursor),
+                    'S' => {
+                        // placing the start on itself turns it clockwise
+                        let direction = if cursor == level.start {
+                            match level.start_direction {
+                                Direction::Up => Direction::Right,
+                                Direction::Right => Direction::Down,
+                                Direction::Down => Direction::Left,
+                                Direction::Left => Direction::Up
+                            }
+                        } else {
+                            level.start_direction.clone()
+                        };
+
+                        level.set_start(&cursor, direction);
+                    },
+                    'U' => if let Some(previous) = undo.pop() {
+                        *level = previous;
+                        undoing = true;
+                        saved = false;
+                    },
+                    _ => {}
+                },
+                KeyCode::Enter => {
+                    status = match level.save(path) {
+                        Ok(_) => {
+                            saved = true;
+                            format!("Saved to {}", path.display())
+                        },
+                        Err(err) => format!("Couldn't save: {}", err)
+                    };
+                },
+                KeyCode::Esc => {
+                    if saved {
+                        break 'editing;
+                    }
+
+                    match menue(stdout, 32, 11, Some("Unsaved changes"), &["SAVE AND LEAVE", "LEAVE WITHOUT SAVING", "KEEP EDITING"])? {
+                        0 => match level.save(path) {
+                            Ok(_) => break 'editing,
+                            Err(err) => status = format!("Couldn't save: {}", err)
+                        },
+                        1 => break 'editing,
+                        _ => {}
+                    }
+
+                    screen.invalidate();
+                },
+                _ => {}
+            }
+        }
+
+        if *level != before && ! undoing {
+            undo.push(before);
+            saved = false;
+            status = String::from(HELP);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,279 @@
+//! Bots running as child processes, written in any language.
+//!
+//! Every tick the game writes the state to the bot's stdin as a single line
+//! of JSON, see [`TickState`]:
+//!
+//! ```text
+//! {"tick":3,"player":0,"width":5,"height":3,"grid":[".....","o.0..","....."],"head":{"x":2,"y":1},"direction":"Right","length":1,"apples":[{"x":0,"y":1}],"snakes":[...]}
+//! ```
+//!
+//! and reads one line from its stdout, the tick it answers followed by the
+//! direction to head into next: `"Up"`, `"Down"`, `"Left"` or `"Right"`,
+//! with or without the quotes and in any case, for example `3 Up`. Lines
+//! answering another tick, like a reply that came in too late for the last
+//! one, are skipped. A reply that doesn't arrive in time or can't be read
+//! is handled according to the bot's [`FailurePolicy`].
+//!
+//! The grid has one string per row and one character per cell: `.` is
+//! empty, `#` a wall, a digit the snake of that player, counting from 0
+//! (only the last digit for players from 10 on), and a letter an apple:
+//! `o` normal, `g` golden, `r` rotten, `s` speed and `t` short-lived, see
+//! [`AppleKind`].
+
+use std::{
+    io::{
+        self,
+        BufRead,
+        BufReader,
+        Write
+    },
+    process::{
+        Child,
+        ChildStdin,
+        Command,
+        Stdio
+    },
+    sync::mpsc::{
+        self,
+        Receiver,
+        RecvTimeoutError
+    },
+    thread,
+    time::{
+        Duration,
+        Instant
+    }
+};
+
+use serde::Serialize;
+
+use crate::{
+    spawner::AppleKind,
+    Controller,
+    Direction,
+    SnakeGame,
+    SnakeGameCord,
+    Tile
+};
+
+/// What happens when a bot doesn't answer in time or answers nonsense.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    /// The snake keeps heading the way it went.
+    #[default]
+    KeepDirection,
+    /// The snake is out of the game, see [`Death::Forfeit`](crate::Death::Forfeit).
+    Forfeit
+}
+
+/// The line written to a bot every tick.
+#[derive(Debug, Clone, Serialize)]
+pub struct TickState {
+    pub tick: u64,
+    /// The player the bot steers.
+    pub player: usize,
+    pub width: usize,
+    pub height: usize,
+    pub grid: Vec<String>,
+    pub head: SnakeGameCord,
+    pub direction: Direction,
+    pub length: u32,
+    pub apples: Vec<SnakeGameCord>,
+    pub snakes: Vec<SnakeState>
+}
+
+/// A snake in a [`TickState`].
+#[derive(Debug, Clone, Serialize)]
+pub struct SnakeState {
+    /// From the tail to the head.
+    pub body: Vec<SnakeGameCord>,
+    pub direction: Direction,
+    pub dead: bool
+}
+
+impl TickState {
+    /// The state of `game` as seen by `player`.
+    pub fn new(game: &SnakeGame, player: usize) -> TickState {
+        let snake = &game.snakes()[player];
+
+        let grid = game.data().iter().map(|row| row.iter().map(|tile| match tile {
+            Tile::Empty => '.',
+            Tile::Apple(AppleKind::Normal) => 'o',
+            Tile::Apple(AppleKind::Golden) => 'g',
+            Tile::Apple(AppleKind::Rotten) => 'r',
+            Tile::Apple(AppleKind::Speed) => 's',
+            Tile::Apple(AppleKind::ShortLived) => 't',
+            Tile::Wall => '#',
+            Tile::Snake(player) => char::from(b'0' + (player % 10) as u8)
+        }).collect()).collect();
+
+        TickState {
+            tick: game.ticks(),
+            player,
+            width: game.width(),
+            height: game.height(),
+            grid,
+            head: snake.head().clone(),
+            direction: snake.direction().clone(),
+            length: snake.len(),
+            apples: game.apples().collect(),
+            snakes: game.snakes().iter().map(|snake| SnakeState {
+                body: snake.body().iter().cloned().collect(),
+                direction: snake.direction().clone(),
+                dead: snake.is_dead()
+            }).collect()
+        }
+    }
+}
+
+/// Reads a bot's reply, see the [module docs](self).
+///
+/// ```
+/// use terminal_snake::{external::parse_reply, Direction};
+///
+/// assert_eq!(parse_reply("3 \"Up\""), Some((3, Direction::Up)));
+/// assert_eq!(parse_reply(" 12  left \r"), Some((12, Direction::Left)));
+/// assert_eq!(parse_reply("Up"), None);
+/// assert_eq!(parse_reply("3 forward"), None);
+/// ```
+pub fn parse_reply(reply: &str) -> Option<(u64, Direction)> {
+    let (tick, direction) = reply.trim().split_once(char::is_whitespace)?;
+
+    let tick = tick.parse().ok()?;
+
+    let direction = direction.trim_start();
+    let direction = direction.strip_prefix('"').and_then(|direction| direction.strip_suffix('"')).unwrap_or(direction);
+
+    Direction::ALL.into_iter()
+        .find(|known| format!("{:?}", known).eq_ignore_ascii_case(direction))
+        .map(|direction| (tick, direction))
+}
+
+/// A [`Controller`] asking a child process for every direction.
+pub struct ExternalBot {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    replies: Receiver<String>,
+    timeout: Duration,
+    policy: FailurePolicy,
+    forfeited: bool,
+    failures: u64
+}
+
+impl ExternalBot {
+    /// How long a bot gets to exit by itself once its stdin is closed before
+    /// it's killed.
+    pub const EXIT_GRACE: Duration = Duration::from_millis(100);
+
+    /// Starts `command` with its stdin and stdout connected to the bot.
+    ///
+    /// Replies taking longer than `timeout` count as failures. Whatever the
+    /// command sets up for stderr stays as it is.
+    pub fn spawn(mut command: Command, timeout: Duration, policy: FailurePolicy) -> io::Result<ExternalBot> {
+        let mut child = command.stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().ok_or_else(|| io::Error::other("the bot has no stdout"))?;
+
+        // a thread reads the replies so waiting for one can time out
+        let (sender, replies) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                match line {
+                    Ok(line) => if sender.send(line).is_err() {
+                        break;
+                    },
+                    Err(_) => break
+                }
+            }
+        });
+
+        Ok(ExternalBot {
+            child,
+            stdin,
+            replies,
+            timeout,
+            policy,
+            forfeited: false,
+            failures: 0
+        })
+    }
+
+    fn ask(&mut self, game: &SnakeGame, player: usize) -> Option<Direction> {
+        let tick = game.ticks();
+
+        let line = serde_json::to_string(&TickState::new(game, player)).ok()?;
+
+        let stdin = self.stdin.as_mut()?;
+
+        if writeln!(stdin, "{}", line).and_then(|_| stdin.flush()).is_err() {
+            // the bot is gone, don't try to write to it again
+            self.stdin = None;
+            return None;
+        }
+
+        let deadline = Instant::now() + self.timeout;
+
+        // replies to earlier ticks came in after their timeout, they're skipped
+        loop {
+            match self.replies.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(reply) => if let Some((_, direction)) = parse_reply(&reply).filter(|(answered, _)| *answered == tick) {
+                    return Some(direction);
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => return None
+            }
+        }
+    }
+}
+
+impl Controller for ExternalBot {
+    fn next_direction(&mut self, game: &SnakeGame, player: usize) -> Direction {
+        if let Some(direction) = self.ask(game, player) {
+            return direction;
+        }
+
+        self.failures += 1;
+
+        if self.policy == FailurePolicy::Forfeit {
+            self.forfeited = true;
+        }
+
+        game.snakes()[player].direction().clone()
+    }
+
+    fn forfeited(&self) -> bool {
+        self.forfeited
+    }
+
+    /// How many replies were late, missing or unreadable since the last reset.
+    fn failures(&self) -> u64 {
+        self.failures
+    }
+
+    fn reset(&mut self) {
+        self.forfeited = false;
+        self.failures = 0;
+    }
+}
+
+impl Drop for ExternalBot {
+    fn drop(&mut self) {
+        // closing stdin lets a well behaved bot exit by itself, one that
+        // doesn't within `EXIT_GRACE` is killed
+        self.stdin = None;
+
+        let deadline = Instant::now() + ExternalBot::EXIT_GRACE;
+
+        while Instant::now() < deadline {
+            match self.child.try_wait() {
+                Ok(Some(_)) => return,
+                Ok(None) => thread::sleep(Duration::from_millis(5)),
+                Err(_) => break
+            }
+        }
+
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,1111 @@
+use std::collections::VecDeque;
+
+use rand::{
+    random,
+    Rng,
+    SeedableRng
+};
+use rand_chacha::ChaCha8Rng;
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    cell_set::CellSet,
+    scoring::Scoring,
+    spawner::{
+        AppleKind,
+        ApplePolicy,
+        AppleSpawner
+    },
+    Config,
+    Layout,
+    Level
+};
+
+/// The direction the snake is heading in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    /// Whether the direction is horizontal or vertical.
+    ///
+    /// The snake can only turn onto a direction of the other kind,
+    /// reversing into itself is never a valid input.
+    pub fn kind(&self) -> DirectionKind {
+        match self {
+            Direction::Up => DirectionKind::Vertical,
+            Direction::Down => DirectionKind::Vertical,
+            _ => DirectionKind::Horizontal
+        }
+    }
+
+    /// The direction pointing the other way.
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left
+        }
+    }
+}
+
+/// The axis a [`Direction`] moves along.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirectionKind {
+    Horizontal,
+    Vertical
+}
+
+/// A cell position on the board, `(0, 0)` is the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnakeGameCord {
+    pub x: usize,
+    pub y: usize
+}
+
+impl SnakeGameCord {
+    /// Moves the coordinate one cell into `direction`.
+    ///
+    /// Panics when moving left of or above `(0, 0)`.
+    pub fn move_direction(&mut self, direction: &Direction) {
+        match direction {
+            Direction::Up => self.y -= 1,
+            Direction::Down => self.y += 1,
+            Direction::Left => self.x -= 1,
+            Direction::Right => self.x += 1
+        }
+    }
+
+    /// Returns a copy of the coordinate moved one cell into `direction`.
+    pub fn moved_direction(&self, direction: &Direction) -> SnakeGameCord {
+        let mut new_cord = self.clone();
+
+        new_cord.move_direction(direction);
+
+        new_cord
+    }
+}
+
+/// What occupies a cell of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Apple(AppleKind),
+    /// A segment of the snake of the player with this number, starting at 0.
+    Snake(usize),
+    Wall
+}
+
+/// What a snake ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Death {
+    /// The edge of the board.
+    Border,
+    Wall,
+    /// Its own body.
+    Itself,
+    /// The body of the snake of this player.
+    Snake(usize),
+    /// The head of the snake of this player, both moved onto the same cell.
+    HeadOn(usize),
+    /// The player gave up, see [`SnakeGame::forfeit`].
+    Forfeit
+}
+
+impl Death {
+    /// A short lowercase name of the kind of death, without the player.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Death::Border => "border",
+            Death::Wall => "wall",
+            Death::Itself => "itself",
+            Death::Snake(_) => "snake",
+            Death::HeadOn(_) => "head_on",
+            Death::Forfeit => "forfeit"
+        }
+    }
+}
+
+/// How a game was won, see [`SnakeGame::victory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Victory {
+    /// The snakes fill every cell that isn't a wall.
+    BoardFilled,
+    /// A snake got as long as [`Config::win_length`].
+    Length,
+    /// A snake survived for [`Config::win_seconds`].
+    Time
+}
+
+impl Victory {
+    /// A short lowercase name of the kind of victory.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Victory::BoardFilled => "board_filled",
+            Victory::Length => "length",
+            Victory::Time => "time"
+        }
+    }
+}
+
+/// One of the snakes on the board.
+#[derive(Debug, Clone)]
+pub struct Snake {
+    body: VecDeque<SnakeGameCord>,
+    direction: Direction,
+    saturation_len: u32,
+    grew_last_tick: bool,
+    death: Option<Death>,
+    // apples eaten by kind, in the order of `AppleKind::ALL`
+    eaten: [u32; 5],
+    score: u32,
+    // apples eaten in a row, each soon after the one before
+    streak: u32,
+    last_apple_tick: Option<u64>
+}
+
+impl Snake {
+    fn new(start: SnakeGameCord, direction: Direction) -> Snake {
+        Snake {
+            body: VecDeque::from([start]),
+            direction,
+            saturation_len: 3,
+            grew_last_tick: false,
+            death: None,
+            eaten: [0; 5],
+            score: 0,
+            streak: 0,
+            last_apple_tick: None
+        }
+    }
+
+    /// The cells of the snake from its tail to its head.
+    pub fn body(&self) -> &VecDeque<SnakeGameCord> {
+        &self.body
+    }
+
+    /// The position of the snake's head.
+    pub fn head(&self) -> &SnakeGameCord {
+        self.body.back().expect("a snake always has a head")
+    }
+
+    /// The current length of the snake.
+    pub fn len(&self) -> u32 {
+        self.body.len() as u32
+    }
+
+    /// Always `false`, a snake is never shorter than its head.
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+
+    /// The direction the snake moved in last.
+    pub fn direction(&self) -> &Direction {
+        &self.direction
+    }
+
+    /// Whether the snake has died, a dead snake stays on the board.
+    pub fn is_dead(&self) -> bool {
+        self.death.is_some()
+    }
+
+    /// What the snake died of, `None` while it's alive.
+    pub fn death(&self) -> Option<Death> {
+        self.death
+    }
+
+    /// How many apples of `kind` the snake ate.
+    pub fn eaten(&self, kind: AppleKind) -> u32 {
+        self.eaten[kind as usize]
+    }
+
+    /// The points of all apples the snake ate, see [`Scoring`].
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// How many apples the snake ate in a row, each within
+    /// [`Scoring::STREAK_TICKS`] of the one before, 0 after a rotten one.
+    pub fn streak(&self) -> u32 {
+        self.streak
+    }
+}
+
+/// The state of a single game of snake.
+///
+/// The board is a grid of [`Tile`]s. Every snake's body is kept separately
+/// as a queue of cells from tail to head, and the free cells and apples are
+/// kept in indexed sets, so a tick costs the same no matter how large the
+/// board is.
+///
+/// The game advances one step per call to [`SnakeGame::tick`], or
+/// [`SnakeGame::tick_all`] when more than one player is playing.
+///
+/// Apples are placed by the game's own RNG, two games with the same seed
+/// that are fed the same directions play out identically.
+#[derive(Clone)]
+pub struct SnakeGame {
+    data: Vec<Vec<Tile>>,
+    snakes: Vec<Snake>,
+    players: usize,
+    free: CellSet,
+    apples: CellSet,
+    // the wall cells, they stay the same until the game starts over
+    walls: Vec<SnakeGameCord>,
+    game_grow_rate: u32,
+    spawner: AppleSpawner,
+    scoring: Scoring,
+    // the cells of short-lived apples and the tick they rot away on, oldest first
+    short_lived: VecDeque<(usize, u64)>,
+    speed_boost_ticks: u64,
+    ticks: u64,
+    // the game time passed, in ticks at the configured speed
+    played: f64,
+    win_length: Option<u32>,
+    win_ticks: Option<u64>,
+    victory: Option<Victory>,
+    easy: bool,
+    borderless: bool,
+    layout: Layout,
+    level: Option<Level>,
+    rng: ChaCha8Rng,
+    seed: u64,
+    fixed_seed: bool
+}
+
+impl SnakeGame {
+    /// How many ticks a [speed apple](AppleKind::Speed) speeds the game up for.
+    pub const SPEED_BOOST_TICKS: u64 = 50;
+
+    /// How much faster the game runs after a speed apple, see [`SnakeGame::speed_factor`].
+    pub const SPEED_BOOST: f64 = 1.5;
+
+    /// How many ticks a [short-lived apple](AppleKind::ShortLived) stays on the board.
+    pub const SHORT_LIVED_TICKS: u64 = 30;
+
+    // how many random free cells an apple that keeps away from the heads
+    // tries before all of them are looked at
+    const SPAWN_TRIES: usize = 32;
+
+    /// Creates an empty board of `width` x `height` cells for one player.
+    ///
+    /// `easy` makes the snake immortal, it loses length instead of dying.
+    /// `borderless` lets the snake wrap around the edges of the board.
+    ///
+    /// The game starts with a random seed, see [`SnakeGame::set_seed`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(width: usize, height: usize, game_grow_rate: u32, max_apple_count: u32, min_apple_count: u32, ticks_between_apple_spawn: u32, easy: bool, borderless: bool) -> SnakeGame {
+        let seed = random();
+
+        let mut game = SnakeGame {
+            data: vec![vec![Tile::Empty; width]; height],
+            snakes: vec![],
+            players: 1,
+            free: CellSet::full(width * height),
+            apples: CellSet::empty(width * height),
+            walls: vec![],
+            game_grow_rate,
+            spawner: AppleSpawner::new(ApplePolicy::Interval, ticks_between_apple_spawn, min_apple_count, max_apple_count, 0),
+            scoring: Scoring::default(),
+            short_lived: VecDeque::new(),
+            speed_boost_ticks: 0,
+            ticks: 0,
+            played: 0.0,
+            win_length: None,
+            win_ticks: None,
+            victory: None,
+            easy,
+            borderless,
+            layout: Layout::Open,
+            level: None,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            seed,
+            fixed_seed: false
+        };
+
+        game.clear();
+
+        game
+    }
+
+    /// Creates a game with the settings of `cfg`.
+    pub fn from_config(cfg: &Config) -> SnakeGame {
+        let mut game = SnakeGame::create(cfg.width / 2, cfg.height, cfg.game_grow_rate, cfg.max_apple_count, cfg.min_apple_count, cfg.ticks_between_apple_spawn, cfg.easy, cfg.borderless);
+
+        game.set_spawner(AppleSpawner::from_config(cfg));
+        game.set_scoring(Scoring::from_config(cfg));
+        game.set_layout(cfg.layout);
+        game.set_players(cfg.players);
+        game.set_goals(cfg.win_length, cfg.win_seconds.map(|seconds| seconds as u64 * cfg.steps_per_second as u64));
+
+        if let Some(seed) = cfg.seed {
+            game.set_seed(seed);
+        }
+
+        game
+    }
+
+    /// Fixes the seed of the RNG and restarts the game from it.
+    ///
+    /// Every [`SnakeGame::clear`] afterwards restarts from the same seed,
+    /// so each retry gets the same apples and walls.
+    ///
+    /// ```
+    /// use terminal_snake::{Config, Direction, SnakeGame};
+    ///
+    /// let mut a = SnakeGame::from_config(&Config::default());
+    /// let mut b = SnakeGame::from_config(&Config::default());
+    ///
+    /// a.set_seed(42);
+    /// b.set_seed(42);
+    ///
+    /// for _ in 0..10 {
+    ///     a.tick(&Direction::Right);
+    ///     b.tick(&Direction::Right);
+    /// }
+    ///
+    /// assert_eq!(a.data(), b.data());
+    /// ```
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.fixed_seed = true;
+        self.clear();
+    }
+
+    /// Replaces the rules for when and where apples appear and restarts the game.
+    pub fn set_spawner(&mut self, spawner: AppleSpawner) {
+        self.spawner = spawner;
+        self.clear();
+    }
+
+    /// Replaces what apples are worth, takes effect with the next apple.
+    pub fn set_scoring(&mut self, scoring: Scoring) {
+        self.scoring = scoring;
+    }
+
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Sets the partial win conditions: a snake `win_length` cells long, or
+    /// surviving `win_ticks` ticks at the configured speed. The game is also
+    /// won by filling the board.
+    ///
+    /// While a speed apple is in effect a tick only counts as the part of a
+    /// tick it takes, see [`SnakeGame::speed_factor`], so the goal takes the
+    /// same time however many speed apples are eaten.
+    ///
+    /// ```
+    /// use terminal_snake::{Config, Direction, SnakeGame, Victory};
+    ///
+    /// let mut game = SnakeGame::from_config(&Config::default());
+    /// game.set_goals(None, Some(5));
+    ///
+    /// for _ in 0..5 {
+    ///     game.tick(&Direction::Right);
+    /// }
+    ///
+    /// assert_eq!(game.victory(), Some(Victory::Time));
+    /// assert!(game.is_over());
+    /// ```
+    pub fn set_goals(&mut self, win_length: Option<u32>, win_ticks: Option<u64>) {
+        self.win_length = win_length;
+        self.win_ticks = win_ticks;
+    }
+
+    /// Creates a game on `level`, with the settings of `cfg` the level doesn't override.
+    pub fn from_level(level: &Level, cfg: &Config) -> SnakeGame {
+        let mut game = SnakeGame::from_config(&level.config(cfg));
+
+        game.level = Some(level.clone());
+        game.clear();
+
+        game
+    }
+
+    /// Replaces the walls of the arena and restarts the game.
+    ///
+    /// Has no effect on a game created by [`SnakeGame::from_level`].
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+        self.clear();
+    }
+
+    /// Sets the number of snakes, at least one and at most one per column,
+    /// and restarts the game.
+    ///
+    /// The first snake starts where the layout or level says, the second one
+    /// at the opposite point of the board heading the other way. Any further
+    /// snakes start two rows below the snake two numbers before them.
+    ///
+    /// ```
+    /// use terminal_snake::{Config, SnakeGame};
+    ///
+    /// let cfg = Config { width: 2, height: 1, players: 2, ..Config::default() };
+    ///
+    /// assert_eq!(SnakeGame::from_config(&cfg).players(), 1);
+    /// ```
+    pub fn set_players(&mut self, players: usize) {
+        self.players = players.clamp(1, self.width().max(1));
+        self.clear();
+    }
+
+    /// The seed the current game was started with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Advances the game by one step with the first snake heading into `direction`.
+    ///
+    /// Any other snakes keep going the way they went, see [`SnakeGame::tick_all`].
+    ///
+    /// Does nothing useful once the game is over, check [`SnakeGame::is_over`].
+    pub fn tick(&mut self, direction: &Direction) {
+        self.tick_all(std::slice::from_ref(direction));
+    }
+
+    /// Advances the game by one step, snake `i` heading into `directions[i]`.
+    ///
+    /// Snakes without a direction keep going the way they went. All snakes
+    /// move at the same time, the board as it was before the move decides
+    /// who crashes. A snake dies when it leaves the board, runs into a wall
+    /// or any snake's body, or when two heads meet on the same cell.
+    ///
+    /// ```
+    /// use terminal_snake::{Config, Direction, SnakeGame};
+    ///
+    /// let cfg = Config { width: 22, height: 5, players: 2, ..Config::default() };
+    /// let mut game = SnakeGame::from_config(&cfg);
+    ///
+    /// // the snakes start on the same row heading towards each other
+    /// game.tick_all(&[Direction::Right, Direction::Left]);
+    /// assert!(! game.is_over());
+    ///
+    /// game.tick_all(&[Direction::Right, Direction::Left]);
+    /// assert!(game.is_over());
+    /// assert_eq!(game.winner(), None);
+    /// ```
+    pub fn tick_all(&mut self, directions: &[Direction]) {
+        /*
+            1. Is move in bounds
+            2. Is move into snake or wall
+            3. Is move into another snake's next head
+            4. Is move into apple
+            5. Put head
+            6. Shorten tail
+            7. Respawn apple
+        */
+
+        // a sped up tick takes less time, like the front-end paces it
+        let tick_time = 1.0 / self.speed_factor();
+
+        let mut moves: Vec<Option<SnakeGameCord>> = vec![None; self.snakes.len()];
+
+        for (i, snake) in self.snakes.iter_mut().enumerate() {
+            if snake.is_dead() {
+                continue;
+            }
+
+            if let Some(direction) = directions.get(i) {
+                snake.direction = direction.clone();
+            }
+        }
+
+        // is move in bounds
+        let mut crashed: Vec<Option<Death>> = vec![None; self.snakes.len()];
+
+        for i in 0..self.snakes.len() {
+            let snake = &self.snakes[i];
+
+            if snake.is_dead() {
+                continue;
+            }
+
+            match self.step(snake.head(), &snake.direction) {
+                Some(pos) => moves[i] = Some(pos),
+                None => crashed[i] = Some(Death::Border)
+            }
+        }
+
+        // is move into snake or wall
+        for (i, pos) in moves.iter().enumerate() {
+            if let Some(pos) = pos {
+                match self.data[pos.y][pos.x] {
+                    Tile::Snake(j) if j == i => crashed[i] = Some(Death::Itself),
+                    Tile::Snake(j) => crashed[i] = Some(Death::Snake(j)),
+                    Tile::Wall => crashed[i] = Some(Death::Wall),
+                    _ => {}
+                }
+            }
+        }
+
+        // is move into another snake's next head
+        for i in 0..moves.len() {
+            for j in i + 1..moves.len() {
+                if moves[i].is_some() && moves[i] == moves[j] {
+                    crashed[i] = Some(Death::HeadOn(j));
+                    crashed[j] = Some(Death::HeadOn(i));
+                }
+            }
+        }
+
+        for (i, death) in crashed.into_iter().enumerate() {
+            if death.is_none() {
+                continue;
+            }
+
+            moves[i] = None;
+
+            if self.easy {
+                self.shrink(i);
+            } else {
+                self.snakes[i].death = death;
+            }
+        }
+
+        for (i, pos) in moves.into_iter().enumerate() {
+            if let Some(pos) = pos {
+                self.move_snake(i, pos);
+            }
+        }
+
+        self.speed_boost_ticks = self.speed_boost_ticks.saturating_sub(1);
+
+        self.rot_apples();
+        self.respawn_apples();
+
+        self.ticks += 1;
+        self.played += tick_time;
+
+        if self.victory.is_none() {
+            self.victory = self.reached_goal();
+        }
+    }
+
+    // the goal the living snakes reached with the last tick, if any
+    fn reached_goal(&self) -> Option<Victory> {
+        let mut alive = self.snakes.iter().filter(|snake| ! snake.is_dead());
+
+        // a game that is lost already can't be won any more
+        if self.is_over() {
+            return None;
+        }
+
+        if self.free.len() == 0 && self.apples.len() == 0 {
+            Some(Victory::BoardFilled)
+        } else if self.win_length.is_some_and(|length| alive.any(|snake| snake.len() >= length)) {
+            Some(Victory::Length)
+        } else if self.win_ticks.is_some_and(|ticks| self.played + 1e-9 >= ticks as f64) {
+            Some(Victory::Time)
+        } else {
+            None
+        }
+    }
+
+    /// Ends the game for the snake of `player`, it stays on the board like a
+    /// crashed snake, even an IMMORTAL one.
+    pub fn forfeit(&mut self, player: usize) {
+        let snake = &mut self.snakes[player];
+
+        if ! snake.is_dead() {
+            snake.death = Some(Death::Forfeit);
+        }
+    }
+
+    fn move_snake(&mut self, i: usize, new_head_pos: SnakeGameCord) {
+        let cell = self.index(&new_head_pos);
+
+        // is move into apple
+        let eaten = match self.data[new_head_pos.y][new_head_pos.x] {
+            Tile::Apple(kind) => Some(kind),
+            _ => None
+        };
+
+        if let Some(kind) = eaten {
+            self.apples.remove(cell);
+
+            let speed_factor = self.speed_factor();
+            let ticks = self.ticks;
+            let snake = &mut self.snakes[i];
+
+            snake.streak = match (kind, snake.last_apple_tick) {
+                (AppleKind::Rotten, _) => 0,
+                (_, Some(tick)) if ticks - tick <= Scoring::STREAK_TICKS => snake.streak + 1,
+                _ => 1
+            };
+            snake.last_apple_tick = Some(ticks);
+
+            snake.eaten[kind as usize] += 1;
+            snake.score = snake.score.saturating_add(self.scoring.points(kind, snake.streak, speed_factor));
+
+            match kind {
+                AppleKind::Rotten => {},
+                AppleKind::Speed => {
+                    snake.saturation_len += self.game_grow_rate;
+                    self.speed_boost_ticks = SnakeGame::SPEED_BOOST_TICKS;
+                },
+                AppleKind::ShortLived => {
+                    snake.saturation_len += self.game_grow_rate;
+                    self.short_lived.retain(|(short_lived, _)| *short_lived != cell);
+                },
+                _ => snake.saturation_len += self.game_grow_rate
+            }
+        }
+
+        // put head
+        self.free.remove(cell);
+        self.data[new_head_pos.y][new_head_pos.x] = Tile::Snake(i);
+        self.snakes[i].body.push_back(new_head_pos);
+
+        // shorten tail
+        let snake = &mut self.snakes[i];
+
+        if (snake.body.len() as u32) <= snake.saturation_len && ! snake.grew_last_tick {
+            snake.grew_last_tick = true;
+        } else {
+            snake.grew_last_tick = false;
+            self.pop_tail(i);
+        }
+
+        if eaten == Some(AppleKind::Rotten) {
+            for _ in 0..self.game_grow_rate {
+                self.shrink(i);
+            }
+        }
+    }
+
+    /// The number of steps between `a` and `b` on an empty board, wrapping
+    /// around the edges on a borderless one.
+    pub fn distance(&self, a: &SnakeGameCord, b: &SnakeGameCord) -> usize {
+        let dx = a.x.abs_diff(b.x);
+        let dy = a.y.abs_diff(b.y);
+
+        if self.borderless {
+            dx.min(self.width() - dx) + dy.min(self.height() - dy)
+        } else {
+            dx + dy
+        }
+    }
+
+    /// The cell one step from `pos` into `direction`, `None` if that is off
+    /// the board. On a borderless board the step wraps around instead.
+    pub fn step(&self, pos: &SnakeGameCord, direction: &Direction) -> Option<SnakeGameCord> {
+        let out_of_bounds = match direction {
+            Direction::Up => pos.y == 0,
+            Direction::Down => pos.y + 1 == self.height(),
+            Direction::Left => pos.x == 0,
+            Direction::Right => pos.x + 1 == self.width()
+        };
+
+        if ! out_of_bounds {
+            return Some(pos.moved_direction(direction));
+        }
+
+        if ! self.borderless {
+            return None;
+        }
+
+        let mut pos = pos.clone();
+
+        match direction {
+            Direction::Up => pos.y = self.height() - 1,
+            Direction::Down => pos.y = 0,
+            Direction::Left => pos.x = self.width() - 1,
+            Direction::Right => pos.x = 0
+        }
+
+        Some(pos)
+    }
+
+    // IMMORTAL: instead of dying the snake stays in place and loses a segment
+    fn shrink(&mut self, i: usize) {
+        let snake = &mut self.snakes[i];
+
+        if snake.saturation_len > 1 {
+            snake.saturation_len -= 1;
+        }
+
+        if snake.body.len() as u32 > snake.saturation_len {
+            self.pop_tail(i);
+        }
+    }
+
+    fn pop_tail(&mut self, i: usize) {
+        if let Some(tail) = self.snakes[i].body.pop_front() {
+            self.data[tail.y][tail.x] = Tile::Empty;
+            self.free.insert(self.index(&tail));
+        }
+    }
+
+    // removes the short-lived apples whose time is up
+    fn rot_apples(&mut self) {
+        while let Some((cell, tick)) = self.short_lived.front().copied() {
+            if tick > self.ticks {
+                break;
+            }
+
+            self.short_lived.pop_front();
+            self.apples.remove(cell);
+            self.free.insert(cell);
+
+            let pos = self.cord(cell);
+            self.data[pos.y][pos.x] = Tile::Empty;
+        }
+    }
+
+    fn respawn_apples(&mut self) {
+        let due = self.spawner.due(self.apple_count(), &mut self.rng);
+
+        'apple_spawning: for _ in 0..due {
+            if ! self.spawn_apple() {
+                break 'apple_spawning;
+            }
+        }
+    }
+
+    // places an apple on a uniformly random free cell, if possible one far
+    // enough from every head, `false` if there is none
+    fn spawn_apple(&mut self) -> bool {
+        let cell = match &self.level {
+            // a level with spawn points only ever gets apples on those
+            Some(level) if ! level.apple_spawns.is_empty() => {
+                let spawns: Vec<usize> = level.apple_spawns.iter().map(|cord| self.index(cord)).filter(|cell| self.free.contains(*cell)).collect();
+
+                if spawns.is_empty() {
+                    return false;
+                }
+
+                spawns[self.rng.gen_range(0..spawns.len())]
+            },
+            _ if self.spawner.min_head_distance() > 0 => {
+                let heads: Vec<SnakeGameCord> = self.snakes.iter().filter(|snake| ! snake.is_dead()).map(|snake| snake.head().clone()).collect();
+
+                // usually most free cells are far enough, a few random picks
+                // find one without looking at the whole board
+                for _ in 0..SnakeGame::SPAWN_TRIES {
+                    let cell = match self.free.choose(&mut self.rng) {
+                        Some(cell) => cell,
+                        None => return false
+                    };
+
+                    if self.far_from(&heads, cell) {
+                        self.place_apple(cell);
+                        return true;
+                    }
+                }
+
+                let far: Vec<usize> = self.free.iter().filter(|cell| self.far_from(&heads, *cell)).collect();
+
+                if far.is_empty() {
+                    match self.free.choose(&mut self.rng) {
+                        Some(cell) => cell,
+                        None => return false
+                    }
+                } else {
+                    far[self.rng.gen_range(0..far.len())]
+                }
+            },
+            _ => match self.free.choose(&mut self.rng) {
+                Some(cell) => cell,
+                None => return false
+            }
+        };
+
+        self.place_apple(cell);
+
+        true
+    }
+
+    // whether `cell` is at least the minimum distance away from all of `heads`
+    fn far_from(&self, heads: &[SnakeGameCord], cell: usize) -> bool {
+        let pos = self.cord(cell);
+
+        heads.iter().all(|head| self.distance(head, &pos) >= self.spawner.min_head_distance())
+    }
+
+    // puts an apple of a random kind on the free `cell`
+    fn place_apple(&mut self, cell: usize) {
+        let kind = self.spawner.weights().choose(&mut self.rng);
+
+        if kind == AppleKind::ShortLived {
+            self.short_lived.push_back((cell, self.ticks + SnakeGame::SHORT_LIVED_TICKS));
+        }
+
+        self.free.remove(cell);
+        self.apples.insert(cell);
+        let pos = self.cord(cell);
+        self.data[pos.y][pos.x] = Tile::Apple(kind);
+    }
+
+    fn index(&self, cord: &SnakeGameCord) -> usize {
+        cord.y * self.width() + cord.x
+    }
+
+    fn cord(&self, index: usize) -> SnakeGameCord {
+        SnakeGameCord {
+            x: index % self.width(),
+            y: index / self.width()
+        }
+    }
+
+    /// Resets the board to the state right after [`SnakeGame::create`],
+    /// keeping all settings.
+    ///
+    /// Without a seed set by [`SnakeGame::set_seed`] the next game gets a
+    /// new random seed.
+    pub fn clear(&mut self) {
+        let (width, height) = (self.width(), self.height());
+
+        for row in &mut self.data {
+            for col in row {
+                *col = Tile::Empty;
+            }
+        }
+
+        self.free = CellSet::full(width * height);
+        self.apples = CellSet::empty(width * height);
+        self.walls.clear();
+        self.snakes.clear();
+
+        if ! self.fixed_seed {
+            self.seed = random();
+        }
+
+        self.rng = ChaCha8Rng::seed_from_u64(self.seed);
+
+        let (start, start_direction, walls) = match &self.level {
+            Some(level) => (level.start.clone(), level.start_direction.clone(), level.walls.clone()),
+            None => (self.layout.start(width, height), Direction::Right, self.layout.walls(width, height, &mut self.rng))
+        };
+
+        let starts = self.starts(start, start_direction);
+
+        for wall in walls {
+            if ! starts.iter().any(|(start, _)| *start == wall) && self.data[wall.y][wall.x] == Tile::Empty {
+                self.free.remove(self.index(&wall));
+                self.data[wall.y][wall.x] = Tile::Wall;
+                self.walls.push(wall);
+            }
+        }
+
+        for (i, (start, direction)) in starts.into_iter().enumerate() {
+            self.free.remove(self.index(&start));
+            self.data[start.y][start.x] = Tile::Snake(i);
+            self.snakes.push(Snake::new(start, direction));
+        }
+
+        self.spawner.reset();
+        self.short_lived.clear();
+        self.speed_boost_ticks = 0;
+        self.ticks = 0;
+        self.played = 0.0;
+        self.victory = None;
+    }
+
+    // where every snake starts given where the first one does, see `set_players`
+    fn starts(&self, start: SnakeGameCord, direction: Direction) -> Vec<(SnakeGameCord, Direction)> {
+        let (width, height) = (self.width(), self.height());
+
+        let mirrored = SnakeGameCord {
+            x: width - 1 - start.x,
+            y: height - 1 - start.y
+        };
+
+        let opposite = direction.opposite();
+
+        let mut starts: Vec<(SnakeGameCord, Direction)> = vec![];
+
+        for i in 0..self.players {
+            let (pos, direction) = match i {
+                0 => (start.clone(), direction.clone()),
+                1 => (mirrored.clone(), opposite.clone()),
+                _ => {
+                    let (pos, direction) = &starts[i - 2];
+
+                    (SnakeGameCord { x: pos.x, y: (pos.y + 2) % height }, direction.clone())
+                }
+            };
+
+            // two snakes can't start on the same cell, the later one moves along
+            // the row, there's a free cell since there are no more snakes than columns
+            let mut pos = pos;
+
+            while starts.iter().any(|(start, _)| *start == pos) {
+                pos.x = (pos.x + 1) % width;
+            }
+
+            starts.push((pos, direction));
+        }
+
+        starts
+    }
+
+    /// The board, indexed as `data()[y][x]`.
+    pub fn data(&self) -> &[Vec<Tile>] {
+        &self.data
+    }
+
+    /// The width of the board in cells.
+    pub fn width(&self) -> usize {
+        self.data[0].len()
+    }
+
+    /// The height of the board in cells.
+    pub fn height(&self) -> usize {
+        self.data.len()
+    }
+
+    /// All snakes, indexed by player number.
+    pub fn snakes(&self) -> &[Snake] {
+        &self.snakes
+    }
+
+    /// The number of snakes on the board.
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    /// The cells of the first snake from its tail to its head.
+    pub fn body(&self) -> &VecDeque<SnakeGameCord> {
+        self.snakes[0].body()
+    }
+
+    /// The position of the first snake's head.
+    pub fn snake_head_pos(&self) -> &SnakeGameCord {
+        self.snakes[0].head()
+    }
+
+    /// The current length of the first snake.
+    pub fn snake_len(&self) -> u32 {
+        self.snakes[0].len()
+    }
+
+    /// The positions of all apples on the board, in no particular order.
+    pub fn apples(&self) -> impl Iterator<Item = SnakeGameCord> + '_ {
+        self.apples.iter().map(|cell| self.cord(cell))
+    }
+
+    /// The positions of all walls on the board.
+    pub fn walls(&self) -> &[SnakeGameCord] {
+        &self.walls
+    }
+
+    /// The kind of the apple at `pos`, `None` if there is none.
+    pub fn apple_kind(&self, pos: &SnakeGameCord) -> Option<AppleKind> {
+        match self.data[pos.y][pos.x] {
+            Tile::Apple(kind) => Some(kind),
+            _ => None
+        }
+    }
+
+    /// How many times faster than the configured speed the game should run
+    /// right now, [`SnakeGame::SPEED_BOOST`] after a speed apple and 1 otherwise.
+    ///
+    /// The game itself only counts ticks, the front-end decides how long one takes.
+    pub fn speed_factor(&self) -> f64 {
+        if self.speed_boost_ticks > 0 {
+            SnakeGame::SPEED_BOOST
+        } else {
+            1.0
+        }
+    }
+
+    /// The number of apples on the board.
+    pub fn apple_count(&self) -> u32 {
+        self.apples.len() as u32
+    }
+
+    /// The number of empty cells on the board.
+    pub fn free_count(&self) -> u32 {
+        self.free.len() as u32
+    }
+
+    /// Whether the first snake has died.
+    pub fn is_dead(&self) -> bool {
+        self.snakes[0].is_dead()
+    }
+
+    /// The number of ticks since the game started.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// How the game was won, `None` while it goes on and when it was lost.
+    pub fn victory(&self) -> Option<Victory> {
+        self.victory
+    }
+
+    /// Whether the game has ended, when it was won, with one player when the
+    /// snake died and with more players when at most one snake is left.
+    pub fn is_over(&self) -> bool {
+        if self.victory.is_some() {
+            return true;
+        }
+
+        let alive = self.snakes.iter().filter(|snake| ! snake.is_dead()).count();
+
+        if self.snakes.len() == 1 {
+            alive == 0
+        } else {
+            alive <= 1
+        }
+    }
+
+    /// The player who won, `None` while the game goes on and when nobody did.
+    ///
+    /// After a [victory](SnakeGame::victory) that is the longest living snake,
+    /// unless several are equally long. Otherwise a one player game has no
+    /// winner and with more players it's the snake that outlived all others,
+    /// `None` if the last snakes died at once.
+    pub fn winner(&self) -> Option<usize> {
+        if self.victory.is_some() {
+            let alive = || self.snakes.iter().enumerate().filter(|(_, snake)| ! snake.is_dead());
+            let longest = alive().map(|(_, snake)| snake.len()).max()?;
+
+            let mut longest = alive().filter(|(_, snake)| snake.len() == longest);
+
+            return match (longest.next(), longest.next()) {
+                (Some((player, _)), None) => Some(player),
+                _ => None
+            };
+        }
+
+        if self.snakes.len() < 2 || ! self.is_over() {
+            return None;
+        }
+
+        self.snakes.iter().position(|snake| ! snake.is_dead())
+    }
+
+    /// Whether the snake loses length instead of dying (IMMORTAL).
+    pub fn is_easy(&self) -> bool {
+        self.easy
+    }
+
+    /// Whether the snake wraps around the edges of the board.
+    pub fn is_borderless(&self) -> bool {
+        self.borderless
+    }
+
+    /// The walls inside the arena.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// The level the game is played on, if any.
+    pub fn level(&self) -> Option<&Level> {
+        self.level.as_ref()
+    }
+
+    /// The direction the first snake heads in at the start of the game.
+    pub fn start_direction(&self) -> &Direction {
+        &self.snakes[0].direction
+    }
+}
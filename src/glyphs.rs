@@ -0,0 +1,145 @@
+use std::env;
+
+use crossterm::style::Color;
+
+use terminal_snake::{
+    config::Renderer,
+    SnakeGame,
+    Tile
+};
+
+use crate::{
+    apple_cell,
+    camera::{
+        importance,
+        Viewport
+    },
+    render::Screen,
+    PLAYER_COLORS
+};
+
+// the renderer actually used for `renderer`, the packed ones fall back to
+// blocks on terminals that don't take unicode
+pub fn usable(renderer: Renderer) -> Renderer {
+    if renderer == Renderer::Blocks || unicode_terminal() {
+        renderer
+    } else {
+        Renderer::Blocks
+    }
+}
+
+// whether the locale says the terminal takes UTF-8, the first of LC_ALL,
+// LC_CTYPE and LANG that's set decides like it does for other programs
+fn unicode_terminal() -> bool {
+    if cfg!(windows) {
+        return true;
+    }
+
+    ["LC_ALL", "LC_CTYPE", "LANG"].iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| ! value.is_empty())
+        .map(|value| {
+            let value = value.to_uppercase();
+
+            value.contains("UTF-8") || value.contains("UTF8")
+        })
+        .unwrap_or(false)
+}
+
+// how many cells wide and high one character of `renderer` is, and how
+// many columns it takes
+pub fn block(renderer: Renderer) -> (usize, usize, usize) {
+    match renderer {
+        Renderer::Blocks => (1, 1, 2),
+        Renderer::HalfBlocks => (1, 2, 1),
+        Renderer::Braille => (2, 4, 1)
+    }
+}
+
+// draws the visible cells of `game` packed into single characters, blocks
+// aren't packed and are drawn by `draw_board`
+pub fn draw_packed(screen: &mut Screen, game: &SnakeGame, viewport: &Viewport, renderer: Renderer) {
+    let (block_width, block_height, _) = block(renderer);
+
+    for row in 0..viewport.rows {
+        for column in 0..viewport.columns {
+            let x = viewport.x + column as usize * block_width;
+            let y = viewport.y + row as usize * block_height;
+
+            let glyph = match renderer {
+                Renderer::HalfBlocks => half_block(color(tile(game, viewport, x, y)), color(tile(game, viewport, x, y + 1))),
+                Renderer::Braille => braille(game, viewport, x, y),
+                Renderer::Blocks => None
+            };
+
+            if let Some((ch, fg, bg)) = glyph {
+                screen.print_colored(viewport.left + 1 + column, viewport.top + 1 + row, &ch.to_string(), fg, bg);
+            }
+        }
+    }
+}
+
+// the tile at `x`, `y`, empty outside of the visible cells, a character
+// on the edge of the viewport may be only partly filled with them
+fn tile(game: &SnakeGame, viewport: &Viewport, x: usize, y: usize) -> Tile {
+    if x >= viewport.x + viewport.width || y >= viewport.y + viewport.height {
+        return Tile::Empty;
+    }
+
+    game.data().get(y).and_then(|row| row.get(x)).copied().unwrap_or(Tile::Empty)
+}
+
+// the color a cell with `tile` is filled with, `None` if it's empty
+fn color(tile: Tile) -> Option<Color> {
+    match tile {
+        Tile::Apple(kind) => Some(apple_cell(kind).1),
+        Tile::Empty => None,
+        Tile::Snake(player) => Some(PLAYER_COLORS[player % PLAYER_COLORS.len()]),
+        Tile::Wall => Some(Color::Grey)
+    }
+}
+
+// the upper half block shows the top cell in the foreground color and the
+// bottom one in the background color
+fn half_block(top: Option<Color>, bottom: Option<Color>) -> Option<(char, Color, Color)> {
+    match (top, bottom) {
+        (None, None) => None,
+        (Some(top), None) => Some(('▀', top, Color::Black)),
+        (None, Some(bottom)) => Some(('▄', bottom, Color::Black)),
+        (Some(top), Some(bottom)) => Some(('▀', top, bottom))
+    }
+}
+
+// the bit of the braille dot for the cell `dx`, `dy` of a 2x4 block, the
+// dots are numbered down the left column first and the bottom row last
+const BRAILLE_DOTS: [[u32; 2]; 4] = [
+    [0x01, 0x08],
+    [0x02, 0x10],
+    [0x04, 0x20],
+    [0x40, 0x80]
+];
+
+// a dot for every cell of the 2x4 block at `x`, `y` that isn't empty, in
+// the color of the most important one, snakes before apples before walls
+fn braille(game: &SnakeGame, viewport: &Viewport, x: usize, y: usize) -> Option<(char, Color, Color)> {
+    let mut dots = 0;
+    let mut shown = Tile::Empty;
+
+    for (dy, row) in BRAILLE_DOTS.iter().enumerate() {
+        for (dx, dot) in row.iter().enumerate() {
+            let tile = tile(game, viewport, x + dx, y + dy);
+
+            if tile != Tile::Empty {
+                dots |= dot;
+            }
+
+            if importance(&tile) > importance(&shown) {
+                shown = tile;
+            }
+        }
+    }
+
+    let color = color(shown)?;
+
+    char::from_u32(0x2800 + dots).map(|ch| (ch, color, Color::Black))
+}
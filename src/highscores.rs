@@ -0,0 +1,138 @@
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs,
+    io,
+    path::Path
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    Config,
+    Victory
+};
+
+/// One finished game in a high score table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub name: String,
+    /// When the game ended, in seconds since the unix epoch.
+    pub date: u64,
+    /// The points scored, see [`Scoring`](crate::scoring::Scoring). Tables
+    /// from before there were scores have 0 points everywhere.
+    #[serde(default)]
+    pub score: u32,
+    /// The length of the snake at the end of the game.
+    pub length: u32,
+    /// How long the game lasted in milliseconds of game time.
+    pub duration_millis: u64,
+    /// How the game was won, `None` if the snake died.
+    #[serde(default)]
+    pub victory: Option<Victory>,
+    /// The settings the game was played with.
+    pub config: Config
+}
+
+impl HighScore {
+    // higher scores rank higher, between equal ones a won game above a lost
+    // one, then the longer snake and then the faster game
+    fn beats(&self, other: &HighScore) -> bool {
+        let key = |score: &HighScore| (score.score, score.victory.is_some(), score.length, Reverse(score.duration_millis));
+
+        key(self) > key(other)
+    }
+}
+
+/// The best games of every mode, see [`Config::mode_hash`].
+///
+/// ```
+/// use terminal_snake::{Config, HighScore, HighScores, Victory};
+///
+/// let cfg = Config::default();
+/// let mut scores = HighScores::default();
+///
+/// let score = |name: &str, score| HighScore {
+///     name: String::from(name),
+///     date: 0,
+///     score,
+///     length: 10,
+///     duration_millis: 1000,
+///     victory: None,
+///     config: cfg.clone()
+/// };
+///
+/// assert_eq!(scores.insert(score("ann", 120)), Some(0));
+/// assert_eq!(scores.insert(score("bob", 200)), Some(0));
+/// assert_eq!(scores.insert(score("cid", 150)), Some(1));
+///
+/// // between equal scores a won game ranks higher
+/// assert_eq!(scores.insert(HighScore { victory: Some(Victory::Time), ..score("dan", 150) }), Some(1));
+///
+/// assert_eq!(scores.get(&cfg)[3].name, "ann");
+/// assert!(scores.get(&Config { easy: true, ..Config::default() }).is_empty());
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    // best first, keyed by the hex mode hash since json keys are strings
+    modes: BTreeMap<String, Vec<HighScore>>
+}
+
+impl HighScores {
+    /// How many games a table keeps.
+    pub const LIMIT: usize = 10;
+
+    fn key(cfg: &Config) -> String {
+        format!("{:016x}", cfg.mode_hash())
+    }
+
+    /// The table of the mode `cfg` belongs to, best first.
+    pub fn get(&self, cfg: &Config) -> &[HighScore] {
+        match self.modes.get(&HighScores::key(cfg)) {
+            Some(scores) => scores,
+            None => &[]
+        }
+    }
+
+    /// Every table that has at least one entry.
+    pub fn tables(&self) -> impl Iterator<Item = &[HighScore]> {
+        self.modes.values().filter(|scores| ! scores.is_empty()).map(|scores| scores.as_slice())
+    }
+
+    /// The place `score` would get in its table, `None` if it doesn't make it.
+    pub fn rank(&self, score: &HighScore) -> Option<usize> {
+        let scores = self.get(&score.config);
+        let rank = scores.iter().position(|other| score.beats(other)).unwrap_or(scores.len());
+
+        if rank < HighScores::LIMIT {
+            Some(rank)
+        } else {
+            None
+        }
+    }
+
+    /// Adds `score` to its table and returns its place there, `None` if it
+    /// didn't make it.
+    pub fn insert(&mut self, score: HighScore) -> Option<usize> {
+        let rank = self.rank(&score)?;
+        let scores = self.modes.entry(HighScores::key(&score.config)).or_default();
+
+        scores.insert(rank, score);
+        scores.truncate(HighScores::LIMIT);
+
+        Some(rank)
+    }
+
+    /// Reads the tables from `path`, no file means there are no scores yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<HighScores> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(HighScores::default()),
+            Err(err) => Err(err)
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, serde_json::to_string(self)?)
+    }
+}
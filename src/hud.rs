@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use crossterm::style::Color;
+
+use terminal_snake::SnakeGame;
+
+use crate::{
+    camera::Viewport,
+    render::Screen
+};
+
+const SEPARATOR: &str = "   ";
+
+// the parts of the hud, most important first
+fn items(game: &SnakeGame, steps_per_second: u32, elapsed: Duration) -> Vec<String> {
+    let mut items = vec![];
+
+    if game.players() == 1 {
+        let snake = &game.snakes()[0];
+
+        items.push(format!("SCORE {}", snake.score()));
+        items.push(format!("LENGTH {}", snake.len()));
+    } else {
+        for (player, snake) in game.snakes().iter().enumerate() {
+            items.push(format!(
+                "P{} SCORE {} LENGTH {}{}",
+                player + 1,
+                snake.score(),
+                snake.len(),
+                if snake.is_dead() { " OUT" } else { "" }
+            ));
+        }
+    }
+
+    let secs = elapsed.as_secs();
+
+    items.push(format!("TIME {}:{:02}", secs / 60, secs % 60));
+    items.push(format!("SPEED {}/s", (steps_per_second as f64 * game.speed_factor()).round()));
+    items.push(format!("APPLES {}", game.apple_count()));
+
+    if game.is_easy() {
+        items.push(String::from("IMMORTAL"));
+    }
+
+    if game.is_borderless() {
+        items.push(String::from("BORDERLESS"));
+    }
+
+    if game.players() == 1 && game.snakes()[0].streak() > 1 {
+        items.push(format!("STREAK x{}", game.snakes()[0].streak()));
+    }
+
+    items
+}
+
+// draws the length, score, time, speed, apple count and modifiers of `game`
+// into the free row above the board in `viewport` and, if they don't all
+// fit, the one below it. What fits nowhere is left out, the hud never covers
+// the board.
+pub fn draw_hud(screen: &mut Screen, game: &SnakeGame, viewport: &Viewport, steps_per_second: u32, elapsed: Duration) {
+    let (s_width, s_height) = screen.size();
+    let margin_left = viewport.left;
+
+    let mut rows = vec![];
+
+    if viewport.top > 0 {
+        rows.push(viewport.top - 1);
+    }
+
+    if viewport.bottom() + 1 < s_height {
+        rows.push(viewport.bottom() + 1);
+    }
+
+    let mut items = items(game, steps_per_second, elapsed).into_iter().peekable();
+
+    for row in rows {
+        let mut line = String::new();
+
+        while let Some(item) = items.peek() {
+            let separator = if line.is_empty() { "" } else { SEPARATOR };
+
+            if line.len() + separator.len() + item.len() > s_width as usize {
+                break;
+            }
+
+            line.push_str(separator);
+            line.push_str(item);
+            items.next();
+        }
+
+        // lined up with the board when it fits, from the left edge otherwise
+        let x = if margin_left as usize + line.len() <= s_width as usize {
+            margin_left
+        } else {
+            0
+        };
+
+        screen.print(x, row, &line, Color::White);
+    }
+}
@@ -0,0 +1,128 @@
+use std::str::FromStr;
+
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+
+use crate::SnakeGameCord;
+
+/// The walls placed inside the arena.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Layout {
+    /// No walls at all.
+    #[default]
+    Open,
+    /// A plus sign in the middle of the arena.
+    Cross,
+    /// Randomly scattered 2x2 blocks, different every game.
+    Pillars,
+    /// Four rooms connected by doorways.
+    Rooms
+}
+
+impl Layout {
+    pub const ALL: [Layout; 4] = [Layout::Open, Layout::Cross, Layout::Pillars, Layout::Rooms];
+
+    /// The name shown in the settings menu.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Open => "OPEN",
+            Layout::Cross => "CROSS",
+            Layout::Pillars => "PILLARS",
+            Layout::Rooms => "ROOMS"
+        }
+    }
+
+    /// The layout after this one, wrapping around.
+    pub fn next(&self) -> Layout {
+        let i = Layout::ALL.iter().position(|layout| layout == self).unwrap_or(0);
+
+        Layout::ALL[(i + 1) % Layout::ALL.len()]
+    }
+
+    /// Where the snake starts on a `width` x `height` board, heading right.
+    ///
+    /// The cells to the right of the start are always free of walls, with
+    /// [`Layout::Pillars`] the first few of them.
+    pub fn start(&self, width: usize, height: usize) -> SnakeGameCord {
+        match self {
+            Layout::Open | Layout::Pillars => SnakeGameCord { x: width / 3, y: height / 2 },
+            Layout::Cross => SnakeGameCord { x: width / 3, y: height / 4 },
+            Layout::Rooms => SnakeGameCord { x: width / 8, y: height / 4 }
+        }
+    }
+
+    /// The wall cells of a `width` x `height` board.
+    ///
+    /// Only [`Layout::Pillars`] uses `rng`.
+    pub fn walls<R: Rng>(&self, width: usize, height: usize, rng: &mut R) -> Vec<SnakeGameCord> {
+        let mut walls = vec![];
+
+        match self {
+            Layout::Open => {},
+            Layout::Cross => {
+                for y in height / 3..=height * 2 / 3 {
+                    walls.push(SnakeGameCord { x: width / 2, y });
+                }
+
+                for x in width / 4..=width * 3 / 4 {
+                    walls.push(SnakeGameCord { x, y: height / 2 });
+                }
+
+                // on low boards the bar reaches up to the start, leave a gap in
+                // it for the start row
+                let start = self.start(width, height);
+
+                walls.retain(|wall| wall.y != start.y || wall.x <= start.x);
+            },
+            Layout::Pillars => {
+                let start = self.start(width, height);
+
+                for _ in 0..width * height / 100 {
+                    let x = rng.gen_range(0..width.max(2) - 1);
+                    let y = rng.gen_range(0..height.max(2) - 1);
+
+                    // keep the start and the first few cells in front of it clear
+                    if x + 3 >= start.x && x <= start.x + 8 && y + 3 >= start.y && y <= start.y + 2 {
+                        continue;
+                    }
+
+                    for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                        if x + dx < width && y + dy < height {
+                            walls.push(SnakeGameCord { x: x + dx, y: y + dy });
+                        }
+                    }
+                }
+            },
+            Layout::Rooms => {
+                // each wall has a three cell wide doorway into each neighbouring room
+                let door = |pos: usize, center: usize| pos + 1 >= center && pos <= center + 1;
+
+                for y in 0..height {
+                    if ! door(y, height / 4) && ! door(y, height * 3 / 4) {
+                        walls.push(SnakeGameCord { x: width / 2, y });
+                    }
+                }
+
+                for x in 0..width {
+                    if x != width / 2 && ! door(x, width / 4) && ! door(x, width * 3 / 4) {
+                        walls.push(SnakeGameCord { x, y: height / 2 });
+                    }
+                }
+            }
+        }
+
+        walls
+    }
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    /// Parses a layout by its [`Layout::name`], ignoring case.
+    fn from_str(s: &str) -> Result<Layout, String> {
+        match Layout::ALL.iter().find(|layout| layout.name().eq_ignore_ascii_case(s)) {
+            Some(layout) => Ok(*layout),
+            None => Err(format!("unknown layout \"{}\", expected one of open, cross, pillars, rooms", s))
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use std::{
+    fmt,
+    fs,
+    io,
+    path::Path
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    Config,
+    Direction,
+    SnakeGameCord
+};
+
+/// A handcrafted arena.
+///
+/// Levels are plain text. A header of `key = value` lines overrides the
+/// matching [`Config`] fields, then a line containing only `map` starts the
+/// arena itself, one line per row and one character per cell:
+///
+/// ```text
+/// # lines starting with '#' are comments in the header
+/// name = The Box
+/// steps_per_second = 12
+/// max_apple_count = 2
+///
+/// map
+/// ..............
+/// .####....####.
+/// .#..........#.
+/// .#..>.......#.
+/// .#......o...#.
+/// .####....####.
+/// ..............
+/// ```
+///
+/// `#` is a wall, `.` is empty, `o` is a spot apples spawn on
+/// and one of `^`, `v`, `<`, `>` is where the snake starts and the way it's
+/// heading. Without any `o` apples spawn anywhere.
+///
+/// The header keys are `name`, `min_apple_count`, `max_apple_count`,
+/// `ticks_between_apple_spawn`, `game_grow_rate`, `steps_per_second`,
+/// `easy`, `borderless` and `seed`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Level {
+    pub name: String,
+    pub width: usize,
+    pub height: usize,
+    pub walls: Vec<SnakeGameCord>,
+    pub start: SnakeGameCord,
+    pub start_direction: Direction,
+    /// The only cells apples spawn on, anywhere if empty.
+    pub apple_spawns: Vec<SnakeGameCord>,
+    pub settings: LevelSettings
+}
+
+/// The [`Config`] fields a level overrides, `None` keeps the player's setting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelSettings {
+    pub min_apple_count: Option<u32>,
+    pub max_apple_count: Option<u32>,
+    pub ticks_between_apple_spawn: Option<u32>,
+    pub game_grow_rate: Option<u32>,
+    pub steps_per_second: Option<u32>,
+    pub easy: Option<bool>,
+    pub borderless: Option<bool>,
+    pub seed: Option<u64>
+}
+
+/// Why a level file couldn't be read, `line` starts at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn error<T>(line: usize, message: String) -> Result<T, ParseError> {
+    Err(ParseError { line, message })
+}
+
+fn parse_value<T: std::str::FromStr>(line: usize, key: &str, value: &str) -> Result<Option<T>, ParseError> {
+    match value.parse() {
+        Ok(value) => Ok(Some(value)),
+        Err(_) => error(line, format!("{} isn't a valid value for {}", value, key))
+    }
+}
+
+impl Level {
+    /// An empty `width` x `height` level, the snake starts like it does in an open arena.
+    pub fn new(name: &str, width: usize, height: usize) -> Level {
+        Level {
+            name: String::from(name),
+            width,
+            height,
+            walls: vec![],
+            start: SnakeGameCord { x: width / 3, y: height / 2 },
+            start_direction: Direction::Right,
+            apple_spawns: vec![],
+            settings: LevelSettings::default()
+        }
+    }
+
+    /// Puts a wall on `cord` or removes the one that is there.
+    ///
+    /// Replaces an apple spawn point, the start can't be walled over.
+    pub fn toggle_wall(&mut self, cord: &SnakeGameCord) {
+        if *cord == self.start {
+            return;
+        }
+
+        self.apple_spawns.retain(|spawn| spawn != cord);
+
+        match self.walls.iter().position(|wall| wall == cord) {
+            Some(i) => {
+                self.walls.remove(i);
+            },
+            None => self.walls.push(cord.clone())
+        }
+    }
+
+    /// Makes `cord` an apple spawn point or removes the one that is there.
+    ///
+    /// Replaces a wall, the start can't be an apple spawn point.
+    pub fn toggle_apple_spawn(&mut self, cord: &SnakeGameCord) {
+        if *cord == self.start {
+            return;
+        }
+
+        self.walls.retain(|wall| wall != cord);
+
+        match self.apple_spawns.iter().position(|spawn| spawn == cord) {
+            Some(i) => {
+                self.apple_spawns.remove(i);
+            },
+            None => self.apple_spawns.push(cord.clone())
+        }
+    }
+
+    /// Moves the start to `cord`, replacing whatever was there.
+    pub fn set_start(&mut self, cord: &SnakeGameCord, direction: Direction) {
+        self.walls.retain(|wall| wall != cord);
+        self.apple_spawns.retain(|spawn| spawn != cord);
+
+        self.start = cord.clone();
+        self.start_direction = direction;
+    }
+
+    /// Parses the text of a level file.
+    ///
+    /// ```
+    /// use terminal_snake::{Direction, Level};
+    ///
+    /// let level = Level::parse("name = Tiny\nmap\n.....\n.>.#.\n..o..\n").unwrap();
+    ///
+    /// assert_eq!((level.width, level.height), (5, 3));
+    /// assert_eq!(level.start_direction, Direction::Right);
+    /// assert_eq!(level.walls.len(), 1);
+    ///
+    /// let err = Level::parse("map\n..>\n..\n").unwrap_err();
+    ///
+    /// assert_eq!(err.line, 3);
+    /// ```
+    pub fn parse(text: &str) -> Result<Level, ParseError> {
+        let mut name = String::from("Unnamed");
+        let mut settings = LevelSettings::default();
+
+        let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line.trim_end_matches('\r')));
+        let mut last_line = 0;
+
+        // header
+        'header: loop {
+            let (line, content) = match lines.next() {
+                Some(line) => line,
+                None => return error(last_line.max(1), String::from("missing map, the arena starts after a line containing only \"map\""))
+            };
+            last_line = line;
+
+            let content = content.trim();
+
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            if content == "map" {
+                break 'header;
+            }
+
+            let (key, value) = match content.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return error(line, format!("expected \"key = value\" or \"map\", found \"{}\"", content))
+            };
+
+            match key {
+                "name" => name = String::from(value),
+                "min_apple_count" => settings.min_apple_count = parse_value(line, key, value)?,
+                "max_apple_count" => settings.max_apple_count = parse_value(line, key, value)?,
+                "ticks_between_apple_spawn" => settings.ticks_between_apple_spawn = parse_value(line, key, value)?,
+                "game_grow_rate" => settings.game_grow_rate = parse_value(line, key, value)?,
+                "steps_per_second" => {
+                    settings.steps_per_second = parse_value(line, key, value)?;
+
+                    if settings.steps_per_second == Some(0) {
+                        return error(line, String::from("steps_per_second must be at least 1"));
+                    }
+                },
+                "easy" => settings.easy = parse_value(line, key, value)?,
+                "borderless" => settings.borderless = parse_value(line, key, value)?,
+                "seed" => settings.seed = parse_value(line, key, value)?,
+                _ => return error(line, format!("unknown setting \"{}\"", key))
+            }
+        }
+
+        // map
+        let map_line = last_line;
+        let mut rows: Vec<(usize, &str)> = lines.collect();
+
+        while rows.last().is_some_and(|(_, row)| row.trim().is_empty()) {
+            rows.pop();
+        }
+
+        if rows.is_empty() {
+            return error(map_line, String::from("the map is empty"));
+        }
+
+        let width = rows[0].1.chars().count();
+        let height = rows.len();
+
+        let mut walls = vec![];
+        let mut apple_spawns = vec![];
+        let mut start = None;
+
+        for (y, (line, row)) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return error(*line, format!("the row is {} cells wide, the first row is {}", row.chars().count(), width));
+            }
+
+            for (x, cell) in row.chars().enumerate() {
+                let cord = SnakeGameCord { x, y };
+
+                let direction = match cell {
+                    '.' => None,
+                    '#' => {
+                        walls.push(cord);
+                        None
+                    },
+                    'o' => {
+                        apple_spawns.push(cord);
+                        None
+                    },
+                    '^' => Some(Direction::Up),
+                    'v' => Some(Direction::Down),
+                    '<' => Some(Direction::Left),
+                    '>' => Some(Direction::Right),
+                    _ => return error(*line, format!("unknown cell '{}' in column {}", cell, x + 1))
+                };
+
+                if let Some(direction) = direction {
+                    if start.is_some() {
+                        return error(*line, format!("second start in column {}, there can only be one", x + 1));
+                    }
+
+                    start = Some((SnakeGameCord { x, y }, direction));
+                }
+            }
+        }
+
+        let (start, start_direction) = match start {
+            Some(start) => start,
+            None => return error(map_line, String::from("the map has no start, mark it with '^', 'v', '<' or '>'"))
+        };
+
+        Ok(Level {
+            name,
+            width,
+            height,
+            walls,
+            start,
+            start_direction,
+            apple_spawns,
+            settings
+        })
+    }
+
+    /// Reads and parses a level file, parse errors are
+    /// [`io::ErrorKind::InvalidData`].
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Level> {
+        Level::parse(&fs::read_to_string(path)?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// The level in the format [`Level::parse`] reads.
+    ///
+    /// ```
+    /// use terminal_snake::Level;
+    ///
+    /// let level = Level::parse("name = Tiny\nmax_apple_count = 3\nmap\n.#..\n.>o.\n").unwrap();
+    ///
+    /// assert_eq!(Level::parse(&level.to_text()).unwrap(), level);
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut text = format!("name = {}\n", self.name);
+
+        let settings = &self.settings;
+
+        let values = [
+            ("min_apple_count", settings.min_apple_count.map(|value| value.to_string())),
+            ("max_apple_count", settings.max_apple_count.map(|value| value.to_string())),
+            ("ticks_between_apple_spawn", settings.ticks_between_apple_spawn.map(|value| value.to_string())),
+            ("game_grow_rate", settings.game_grow_rate.map(|value| value.to_string())),
+            ("steps_per_second", settings.steps_per_second.map(|value| value.to_string())),
+            ("easy", settings.easy.map(|value| value.to_string())),
+            ("borderless", settings.borderless.map(|value| value.to_string())),
+            ("seed", settings.seed.map(|value| value.to_string()))
+        ];
+
+        for (key, value) in values {
+            if let Some(value) = value {
+                text.push_str(&format!("{} = {}\n", key, value));
+            }
+        }
+
+        let mut rows = vec![vec!['.'; self.width]; self.height];
+
+        for wall in &self.walls {
+            rows[wall.y][wall.x] = '#';
+        }
+
+        for spawn in &self.apple_spawns {
+            rows[spawn.y][spawn.x] = 'o';
+        }
+
+        rows[self.start.y][self.start.x] = match self.start_direction {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>'
+        };
+
+        text.push_str("\nmap\n");
+
+        for row in rows {
+            text.extend(row);
+            text.push('\n');
+        }
+
+        text
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    /// `cfg` with the size of the level and the level's settings applied.
+    pub fn config(&self, cfg: &Config) -> Config {
+        let mut cfg = cfg.clone();
+
+        cfg.width = self.width * 2;
+        cfg.height = self.height;
+
+        let settings = &self.settings;
+
+        if let Some(value) = settings.min_apple_count {
+            cfg.min_apple_count = value;
+        }
+        if let Some(value) = settings.max_apple_count {
+            cfg.max_apple_count = value;
+        }
+        if let Some(value) = settings.ticks_between_apple_spawn {
+            cfg.ticks_between_apple_spawn = value;
+        }
+        if let Some(value) = settings.game_grow_rate {
+            cfg.game_grow_rate = value;
+        }
+        if let Some(value) = settings.steps_per_second {
+            cfg.steps_per_second = value;
+        }
+        if let Some(value) = settings.easy {
+            cfg.easy = value;
+        }
+        if let Some(value) = settings.borderless {
+            cfg.borderless = value;
+        }
+        if settings.seed.is_some() {
+            cfg.seed = settings.seed;
+        }
+
+        cfg
+    }
+}
@@ -0,0 +1,63 @@
+//! The game engine behind `terminal-snake`.
+//!
+//! The engine has no terminal dependency, it only simulates the board.
+//! A front-end creates a [`SnakeGame`], feeds it one [`Direction`] per
+//! [`SnakeGame::tick`] and draws [`SnakeGame::data`] however it likes.
+//!
+//! ```
+//! use terminal_snake::{Config, Direction, SnakeGame};
+//!
+//! let mut game = SnakeGame::from_config(&Config::default());
+//!
+//! while ! game.is_dead() {
+//!     game.tick(&Direction::Right);
+//! }
+//!
+//! assert_eq!(game.snake_head_pos().x, game.width() - 1);
+//! ```
+//!
+//! Instead of passing directions by hand a [`Controller`] can steer, either
+//! a player's [`InputQueue`] or one of the [`bots`]. The [`simulation`]
+//! module plays whole games with a controller, for example to compare bots.
+//!
+//! When and where apples appear is up to the game's
+//! [`AppleSpawner`](spawner::AppleSpawner), what they're worth to its
+//! [`Scoring`](scoring::Scoring).
+//!
+//! The game has no notion of time besides its ticks, a front-end can pace
+//! them with a [`Ticker`](ticker::Ticker).
+//!
+//! Build without default features to get only the engine, without the
+//! terminal front-end and its dependencies.
+
+pub mod bots;
+mod cell_set;
+pub mod config;
+pub mod controller;
+pub mod external;
+pub mod game;
+pub mod highscores;
+pub mod layout;
+pub mod level;
+pub mod replay;
+pub mod scoring;
+pub mod simulation;
+pub mod spawner;
+pub mod ticker;
+
+pub use config::Config;
+pub use controller::{Controller, InputQueue};
+pub use game::{
+    Death,
+    Direction,
+    DirectionKind,
+    Snake,
+    SnakeGame,
+    SnakeGameCord,
+    Tile,
+    Victory
+};
+pub use highscores::{HighScore, HighScores};
+pub use layout::Layout;
+pub use level::Level;
+pub use replay::Replay;
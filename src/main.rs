@@ -1,17 +1,7 @@
-use rand::random;
 use crossterm::{
     execute,
-    cursor::{
-        Hide,
-        Show,
-        MoveTo
-    },
+    cursor::MoveTo,
     terminal::{
-        enable_raw_mode,
-        disable_raw_mode,
-        EnterAlternateScreen,
-        LeaveAlternateScreen,
-        SetTitle,
         size,
         Clear,
         ClearType
@@ -19,12 +9,10 @@ use crossterm::{
     style::{
         SetBackgroundColor,
         SetForegroundColor,
-        ResetColor,
         Color,
         Print
     },
     event::{
-        read,
         poll,
         Event,
         KeyCode
@@ -34,348 +22,240 @@ use crossterm::{
 use std::{
     time::{
         Duration, 
-        Instant
+        Instant,
+        SystemTime,
+        UNIX_EPOCH
     },
     io::{
+        self,
         stdout, 
         Stdout
-    }
+    },
+    fs,
+    path::PathBuf,
+    process::Stdio,
+    thread
 };
 
-use serde::{Serialize, Deserialize};
-
-#[derive(Clone, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right
-}
-
-impl Direction {
-    fn kind(&self) -> DirectionKind {
-        match self {
-            Direction::Up => DirectionKind::Vertical,
-            Direction::Down => DirectionKind::Vertical,
-            _ => DirectionKind::Horizontal
-        }
-    }
-}
-
-#[derive(Clone, PartialEq, Eq)]
-enum DirectionKind {
-    Horizontal,
-    Vertical
-}
+mod camera;
+mod cli;
+mod editor;
+mod glyphs;
+mod hud;
+mod render;
+mod scoreboard;
+mod terminal;
+
+use clap::Parser;
+
+use cli::{
+    Cli,
+    Command,
+    Format,
+    Overrides
+};
+use camera::{
+    draw_minimap,
+    Viewport
+};
+use editor::edit_level;
+use hud::draw_hud;
+use render::Screen;
+use scoreboard::{
+    print_highscores,
+    show_highscores
+};
+use terminal::{
+    read,
+    TerminalGuard
+};
 
-#[derive(Clone, PartialEq, Eq)]
-struct SnakeGameCord {
-    x: usize,
-    y: usize
-}
+use serde::Serialize;
 
-impl SnakeGameCord {
-    fn move_direction(&mut self, direction: &Direction) {
-        match direction {
-            Direction::Up => self.y -= 1,
-            Direction::Down => self.y += 1,
-            Direction::Left => self.x -= 1,
-            Direction::Right => self.x += 1
-        }
-    }
+use terminal_snake::{
+    bots,
+    config::Renderer,
+    external::{
+        ExternalBot,
+        FailurePolicy
+    },
+    simulation::{
+        self,
+        Summary
+    },
+    spawner::{
+        AppleKind,
+        ApplePolicy,
+        AppleWeights
+    },
+    ticker::{
+        SystemClock,
+        Ticker
+    },
+    Config,
+    Controller,
+    Direction,
+    HighScore,
+    HighScores,
+    InputQueue,
+    Layout,
+    Level,
+    Replay,
+    SnakeGame,
+    SnakeGameCord,
+    Tile,
+    Victory
+};
 
-    fn moved_direction(&self, direction: &Direction) -> SnakeGameCord {
-        let mut new_cord = self.clone();
+fn calculate_margins(width: u16, height: u16) -> Result<(u16, u16)> {
+    let (s_width, s_height) = size()?;
 
-        new_cord.move_direction(direction);
+    let width = if width + 2 > s_width {
+        s_width.saturating_sub(2)
+    } else {
+        width
+    };
+    let height = if height + 2 > s_height {
+        s_height.saturating_sub(2)
+    } else {
+        height
+    };
 
-        new_cord
-    }
+    Ok((s_width.saturating_sub(width + 2) / 2, s_height.saturating_sub(height + 2) / 2))
 }
 
-struct SnakeGame {
-    data: Vec<Vec<i32>>,
-    snake_head_pos: SnakeGameCord,
-    snake_len: u32,
-    snake_saturation_len: u32,
-    grew_last_tick: bool,
-    dead: bool,
-    game_grow_rate: u32,
-    max_apple_count: u32,
-    min_apple_count: u32,
-    ticks_between_apple_spawn: u32,
-    ticks_since_last_apple_spawned: u32,
-    easy: bool,
-    borderless: bool
+fn display_game(stdout: &mut Stdout, screen: &mut Screen, game: &SnakeGame, cfg: &Config, elapsed: Duration) -> Result<()> {
+    screen.begin()?;
+    draw_frame(screen, game, cfg, elapsed)?;
+    screen.present(stdout)
 }
 
-impl SnakeGame {
-    fn create(width: usize, height: usize, game_grow_rate: u32, max_apple_count: u32, min_apple_count: u32, ticks_between_apple_spawn: u32, easy: bool, borderless: bool) -> SnakeGame {
-        let row = vec![0].repeat(width);
+// the board of a game being played with the hud and, if it's on, the minimap
+fn draw_frame(screen: &mut Screen, game: &SnakeGame, cfg: &Config, elapsed: Duration) -> Result<()> {
+    let viewport = draw_game(screen, game, cfg.renderer)?;
 
-        let mut data = vec![];
-
-        for _ in 0..height {
-            data.push(row.clone());
-        }
+    draw_hud(screen, game, &viewport, cfg.steps_per_second, elapsed);
 
-        SnakeGame { 
-            data, 
-            snake_head_pos: SnakeGameCord { x: width/3, y: height/2 }, 
-            snake_len: 0,
-            snake_saturation_len: 3,
-            grew_last_tick: true,
-            dead: false,
-            game_grow_rate,
-            max_apple_count,
-            min_apple_count,
-            ticks_between_apple_spawn,
-            ticks_since_last_apple_spawned: 0,
-            easy,
-            borderless
-        }
+    if cfg.minimap {
+        draw_minimap(screen, game, &viewport);
     }
 
-    fn tick(&mut self, direction: &Direction) {
-        /*
-            1. Is move in bounds
-            2. Is move into snake
-            3. Is move into apple
-            4. Shorten tail
-            5. Put head
-            6. Respawn apple
-        */
-
-        // is move in bounds
-        let new_head_pos = if self.would_move_out_of_bounds(direction) {
-            if self.borderless {
-                let mut x = self.snake_head_pos.x as i64;
-                let mut y = self.snake_head_pos.y as i64;
-
-                match direction {
-                    Direction::Up => y -= 1,
-                    Direction::Down => y += 1,
-                    Direction::Left => x -= 1,
-                    Direction::Right => x += 1
-                }
-
-                x %= self.data[0].len() as i64;
-                y %= self.data.len() as i64;
-
-                if x < 0 {
-                    x = (self.data[0].len() - 1) as i64;
-                }
-
-                if y < 0 {
-                    y = (self.data.len() - 1) as i64;
-                }
-
-                SnakeGameCord {
-                    x: x as usize,
-                    y: y as usize
-                }
-            } else {
-                if ! self.easy {
-                    self.dead = true;
-                    return;
-                }
-
-                self.snake_saturation_len -= 1;
-                self.snake_len -= 1;
-                self.snake_head_pos.clone()
-            }
-        } else {
-            self.snake_head_pos.moved_direction(direction)
-        };
-
-        let value_at_new_head_pos = self.data[new_head_pos.y][new_head_pos.x];
+    Ok(())
+}
 
-        // is move into body
-        if value_at_new_head_pos > 0 {
-            if self.easy {
-                self.snake_saturation_len -= 1;
-                self.snake_len -= 1;
-            } else {
-                self.dead = true;
-                return;
-            }
-        } else {
-            // put head
-            self.data[new_head_pos.y][new_head_pos.x] = self.snake_len as i32;
-            self.snake_head_pos = new_head_pos;
-        }
+// the score and length of every snake, with the streak if there is one
+fn score_line(game: &SnakeGame) -> String {
+    let snakes: Vec<String> = game.snakes().iter().enumerate().map(|(player, snake)| {
+        let mut txt = format!("SCORE {}  LENGTH {}", snake.score(), snake.len());
 
-        // is move into apple
-        if value_at_new_head_pos == -1 {
-            self.snake_saturation_len += self.game_grow_rate;
+        if snake.streak() > 1 {
+            txt.push_str(&format!("  STREAK x{}", snake.streak()));
         }
 
-        // shorten tail
-        if self.snake_len < self.snake_saturation_len && ! self.grew_last_tick {
-            self.snake_len += 1;
-            self.grew_last_tick = true;
-        } else {
-            self.shorten_snake();
-            self.grew_last_tick = false;
+        if game.players() > 1 {
+            txt = format!("P{} {}", player + 1, txt);
         }
 
-        // respawn apple
-        if self.count_apples() < self.max_apple_count {
-            self.ticks_since_last_apple_spawned += 1;
-
-            'apple_spawning: while self.ticks_since_last_apple_spawned > self.ticks_between_apple_spawn || self.count_apples() < self.min_apple_count {
-                self.ticks_since_last_apple_spawned = 0;
+        txt
+    }).collect();
 
-                if ! self.spawn_apple() {
-                    break 'apple_spawning;
-                }
-            }
-        }
-
-    }
-
-    fn would_move_out_of_bounds(&self, direction: &Direction) -> bool {
-        match direction {
-            Direction::Up => if self.snake_head_pos.y == 0 {
-                return true
-            },
-            Direction::Down => if self.snake_head_pos.y + 1 == self.data.len() {
-                return true
-            },
-            Direction::Left => if self.snake_head_pos.x == 0 {
-                return true
-            },
-            Direction::Right => if self.snake_head_pos.x + 1 == self.data[0].len() {
-                return true
-            }
-        }
-        
-        false
-    }
+    snakes.join("    ")
+}
 
-    fn shorten_snake(&mut self) {
-        for row in &mut self.data {
-            for col in row {
-                if *col > 0 {
-                    *col -= 1;
-                }
-            }
-        }
+// the color of each player's snake, repeating once there are more players
+pub(crate) const PLAYER_COLORS: [Color; 4] = [Color::Green, Color::Cyan, Color::Yellow, Color::Magenta];
+
+// draws the part of the board around the snake that fits on the screen with
+// `renderer`, or with blocks if the terminal can't show it
+fn draw_game(screen: &mut Screen, game: &SnakeGame, renderer: Renderer) -> Result<Viewport> {
+    let renderer = glyphs::usable(renderer);
+
+    if renderer == Renderer::Blocks {
+        return draw_board(screen, game.width(), game.height(), game.is_borderless(), &camera::focus(game), |x, y| match game.data()[y][x] {
+            Tile::Apple(kind) => Some(apple_cell(kind)),
+            Tile::Empty => None,
+            Tile::Snake(player) => Some(("[]", PLAYER_COLORS[player % PLAYER_COLORS.len()], Color::Black)),
+            Tile::Wall => Some(("##", Color::Grey, Color::Black))
+        });
     }
 
-    fn count_apples(&self) -> u32 {
-        let mut counter : u32 = 0;
-
-        for row in &self.data {
-            for col in row {
-                if *col == -1 {
-                    counter += 1;
-                }
-            }
-        }
-
-        counter
-    }
+    let viewport = Viewport::new(screen, game.width(), game.height(), &camera::focus(game), renderer);
 
-    fn count_free(&self) -> u32 {
-        let mut counter : u32 = 0;
+    draw_border(screen, &viewport, game.is_borderless());
+    glyphs::draw_packed(screen, game, &viewport, renderer);
 
-        for row in &self.data {
-            for col in row {
-                if *col == 0 {
-                    counter += 1;
-                }
-            }
-        }
+    Ok(viewport)
+}
 
-        counter
+// the text and colors of an apple of `kind`
+pub(crate) fn apple_cell(kind: AppleKind) -> (&'static str, Color, Color) {
+    match kind {
+        AppleKind::Normal => ("()", Color::Red, Color::Black),
+        AppleKind::Golden => ("<>", Color::Yellow, Color::Black),
+        AppleKind::Rotten => ("{}", Color::DarkGreen, Color::Black),
+        AppleKind::Speed => (">>", Color::Blue, Color::Black),
+        AppleKind::ShortLived => ("::", Color::Magenta, Color::Black)
     }
+}
 
-    fn spawn_apple(&mut self) -> bool {
-        let free = self.count_free();
-        let pos = random::<u32>() % free;
-
-        let mut count = 1;
-
-        for row in &mut self.data {
-            for col in row {
-                if *col == 0 {
-                    if count == pos {
-                        *col = -1;
-                        return true;
-                    }
-                    count += 1;
-                }
-            }
-        }
+// the shortest time between two frames of a game, about 60 per second
+const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
 
-        false
-    }
+// how long the next tick of `game` takes, shorter while a speed apple is in effect
+fn tick_delay(steps_per_second: u32, game: &SnakeGame) -> Duration {
+    Duration::from_secs_f64(1.0 / (steps_per_second.max(1) as f64 * game.speed_factor()))
+}
 
-    fn clear(&mut self) {
-        for row in &mut self.data {
-            for col in row {
-                *col = 0;
+// draws a bordered board of two character wide cells, `cell` gives the text
+// and colors of the cell at `x`, `y` or `None` if it's empty
+//
+// a board larger than the screen is cut down to the cells around `focus`,
+// the sides of the border where it goes on are dimmed
+fn draw_board<F>(screen: &mut Screen, width: usize, height: usize, borderless: bool, focus: &SnakeGameCord, cell: F) -> Result<Viewport>
+where
+    F: Fn(usize, usize) -> Option<(&'static str, Color, Color)>
+{
+    let viewport = Viewport::new(screen, width, height, focus, Renderer::Blocks);
+
+    draw_border(screen, &viewport, borderless);
+
+    for y in 0..viewport.height {
+        let screen_y = y as u16 + 1 + viewport.top;
+
+        for x in 0..viewport.width {
+            if let Some((text, fg, bg)) = cell(viewport.x + x, viewport.y + y) {
+                screen.print_colored(viewport.left + 1 + x as u16 * 2, screen_y, text, fg, bg);
             }
         }
-
-        self.snake_head_pos = SnakeGameCord { x: self.data[0].len() / 3, y: self.data.len() / 2 };
-        self.snake_len = 0;
-        self.snake_saturation_len = 3;
-        self.grew_last_tick = true;
-        self.dead = false;
     }
-}
-
-fn calculate_margins(width: u16, height: u16) -> Result<(u16, u16)> {
-    let (s_width, s_height) = size()?;
-
-    let width = if width + 2 > s_width {
-        s_width - 2
-    } else {
-        width
-    };
-    let height = if height + 2 > s_height {
-        s_height - 2
-    } else {
-        height
-    };
 
-    Ok(((s_width - width - 2) / 2, (s_height - height - 2) / 2))
+    Ok(viewport)
 }
 
-fn display_game(stdout: &mut Stdout, game: &SnakeGame) -> Result<()> {
-    let (margin_left, margin_top) = calculate_margins(game.data[0].len() as u16 * 2, game.data.len() as u16)?;
+// draws the border around `viewport`, dimmed on the sides where the board goes on
+fn draw_border(screen: &mut Screen, viewport: &Viewport, borderless: bool) {
+    let (cut_left, cut_right, cut_top, cut_bottom) = viewport.cut_edges();
 
-    let border = if game.borderless {
+    let border = if borderless {
         "@"
     } else {
         "#"
     };
-    
-    execute!(stdout, MoveTo(margin_left, margin_top), Print(String::from(border).repeat(game.data[0].len() * 2 + 2)))?;
 
-    for (y, row) in game.data.iter().enumerate() {
-        execute!(stdout, MoveTo(margin_left, y as u16 + 1 + margin_top), Print(border))?;
+    let color = |cut: bool| if cut { Color::DarkGrey } else { Color::Grey };
 
-        for col in row {
-            match *col {
-                -1 => execute!(stdout, SetForegroundColor(Color::Red), Print("()"), SetForegroundColor(Color::Grey))?,
-                0 => execute!(stdout, Print("  "))?,
-                _ => execute!(stdout, SetForegroundColor(Color::Green), Print("[]"), SetForegroundColor(Color::Grey))?
-            }
-        }
+    let bar = String::from(border).repeat(viewport.columns as usize + 2);
 
-        execute!(stdout, Print(border))?;
-    }
+    screen.print(viewport.left, viewport.top, &bar, color(cut_top));
 
-    execute!(stdout, MoveTo(margin_left, game.data.len() as u16 + 1 + margin_top), Print(String::from(border).repeat(game.data[0].len() * 2 + 2)))?;
+    for y in viewport.top + 1..viewport.bottom() {
+        screen.print(viewport.left, y, border, color(cut_left));
+        screen.print(viewport.right(), y, border, color(cut_right));
+    }
 
-    Ok(())
+    screen.print(viewport.left, viewport.bottom(), &bar, color(cut_bottom));
 }
 
-
-
 // the width and height are the inner width and height
 fn menue(stdout: &mut Stdout, width: u16, height: u16, title: Option<&str>, items: &[&str]) -> Result<usize> {
     let mut selected = 0;
@@ -404,19 +284,16 @@ fn menue(stdout: &mut Stdout, width: u16, height: u16, title: Option<&str>, item
         }
 
         // title
-        match &title {
-            Some(title_text) => {
-                execute!(
-                    stdout, 
-                    MoveTo(margin_left + (width - title_text.len() as u16) / 2 + 1, margin_top + 1), 
-                    SetForegroundColor(Color::Green), 
-                    Print(title_text), 
-                    SetForegroundColor(Color::White)
-                )?;
-                height -= 1;
-                margin_top += 1;
-            },
-            _ => {}
+        if let Some(title_text) = title {
+            execute!(
+                stdout, 
+                MoveTo(margin_left + (width - title_text.len() as u16) / 2 + 1, margin_top + 1), 
+                SetForegroundColor(Color::Green), 
+                Print(title_text), 
+                SetForegroundColor(Color::White)
+            )?;
+            height -= 1;
+            margin_top += 1;
         }
 
         // draw entries
@@ -435,129 +312,769 @@ fn menue(stdout: &mut Stdout, width: u16, height: u16, title: Option<&str>, item
         // process events
         match read()? {
             Event::Key(keyevent) => match keyevent.code {
-                KeyCode::Down => if selected < items.len() - 1 {
-                    selected += 1;
-                },
-                KeyCode::Up => if selected > 0 {
-                    selected -= 1;
-                },
+                KeyCode::Down if selected < items.len() - 1 => selected += 1,
+                KeyCode::Up => selected = selected.saturating_sub(1),
                 KeyCode::Enter => {
                     return Ok(selected);
                 },
                 _ => {}
             },
+            // the menue moves with the new margins, the old one has to go
+            Event::Resize(_, _) => execute!(stdout, Clear(ClearType::All))?,
             _ => {}
         }
     }
 }
 
-fn play_game(stdout: &mut Stdout, game: &mut SnakeGame, steps_per_second: u32) -> Result<()> {
-    let millis_delay = 1000 / steps_per_second;
+// what the player wants to do after leaving a game
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AfterGame {
+    MainMenu,
+    Quit
+}
+
+// plays `game` until the player leaves it, the SETTINGS of the pause screen change `settings`
+//
+// `opponent` steers the last snake instead of a player at the keyboard
+fn play_game(stdout: &mut Stdout, game: &mut SnakeGame, cfg: &Config, opponent: Option<Box<dyn Controller>>, settings: &mut Config) -> Result<AfterGame> {
+    let humans = game.players() - opponent.iter().count();
+
+    // alone both WASD and the arrows steer, with two players WASD steers the
+    // first snake and the arrows the second
+    let arrows_player = if humans > 1 { 1 } else { 0 };
+
+    let mut controllers: Vec<Box<dyn Controller>> = (0..humans).map(|_| Box::new(InputQueue::new()) as Box<dyn Controller>).collect();
+    controllers.extend(opponent);
     
     'retry: loop {
-        let mut direction = Direction::Right;
+        for controller in controllers.iter_mut() {
+            controller.reset();
+        }
+
+        let mut replay = Replay::new(cfg, game);
 
-        let mut queue = vec![];
+        let mut screen = Screen::new();
 
-        execute!(stdout, Clear(ClearType::All))?;
+        let mut duration = Duration::ZERO;
 
         for _ in 0..3 {
-            game.tick(&direction);
+            let directions: Vec<Direction> = game.snakes().iter().map(|snake| snake.direction().clone()).collect();
+
+            duration += tick_delay(cfg.steps_per_second, game);
+            replay.record(&directions);
+            game.tick_all(&directions);
         }
 
-        while ! game.dead {
-            let now = Instant::now();
+        let mut ticker = Ticker::new(SystemClock::new());
+        let mut last_frame: Option<Instant> = None;
+        let mut generation = terminal::generation();
 
-            while poll(Duration::from_secs(0))? {
-                
-                match read()? {
+        while ! game.is_over() {
+            let mut resized = false;
+            let mut paused = false;
+
+            // waits for input until the next tick is due
+            if poll(ticker.until_next())? {
+                paused = match read()? {
                     Event::Key(key_event) => {
                         match key_event.code {
-                            KeyCode::Up => queue.push(Direction::Up),
-                            KeyCode::Down => queue.push(Direction::Down),
-                            KeyCode::Left => queue.push(Direction::Left),
-                            KeyCode::Right => queue.push(Direction::Right),
+                            KeyCode::Up => controllers[arrows_player].input(Direction::Up),
+                            KeyCode::Down => controllers[arrows_player].input(Direction::Down),
+                            KeyCode::Left => controllers[arrows_player].input(Direction::Left),
+                            KeyCode::Right => controllers[arrows_player].input(Direction::Right),
                             KeyCode::Char(c) => match c.to_ascii_uppercase() {
-                                'W' => queue.push(Direction::Up),
-                                'A' => queue.push(Direction::Left),
-                                'S' => queue.push(Direction::Down),
-                                'D' => queue.push(Direction::Right),
-                                _ => {}
-                            },
-                            KeyCode::Esc => match menue(stdout, 32, 9, None, &["CONTINUE", "EXIT"])? {
-                                0 => {},
-                                1 => break 'retry,
+                                'W' => controllers[0].input(Direction::Up),
+                                'A' => controllers[0].input(Direction::Left),
+                                'S' => controllers[0].input(Direction::Down),
+                                'D' => controllers[0].input(Direction::Right),
                                 _ => {}
                             },
                             _ => {}
                         }
+
+                        key_event.code == KeyCode::Esc
                     },
-                    _ => {}
-                }
+                    // alt-tabbing away shouldn't kill the snake
+                    Event::FocusLost => cfg.pause_on_focus_loss,
+                    Event::Resize(_, _) => {
+                        resized = true;
+                        cfg.pause_on_resize
+                    },
+                    _ => false
+                };
             }
 
-            let mut iter = queue.iter();
+            // the game was suspended with Ctrl+Z or from the shell, after
+            // coming back the player has to pick it up again
+            if terminal::generation() != generation {
+                paused = true;
+            }
 
-            'select_next_relevant_queue_result: loop {
-                match iter.next() {
-                    Some(dir) => if dir.kind() != direction.kind() {
-                        direction = dir.clone();
-                        break 'select_next_relevant_queue_result;
-                    },
-                    None => {
-                        break 'select_next_relevant_queue_result;
+            if paused {
+                'paused: loop {
+                    match pause(stdout, &mut screen, game, cfg, duration, settings)? {
+                        0 => if countdown(stdout, &mut screen, game, cfg, duration)? {
+                            break 'paused;
+                        },
+                        1 => {
+                            game.clear();
+                            continue 'retry;
+                        },
+                        3 => break 'retry,
+                        _ => return Ok(AfterGame::Quit)
                     }
                 }
+
+                last_frame = None;
+                generation = terminal::generation();
+                ticker.resync(tick_delay(cfg.steps_per_second, game));
             }
 
-            let mut new_queue = vec![];
+            // every tick that's due, several at once when the ticks are
+            // shorter than the wait for input can be
+            let mut ticked = false;
 
-            'collect_remaining: loop {
-                match iter.next() {
-                    Some(dir) => {
-                        new_queue.push(dir.clone());
+            'ticking: while ! game.is_over() {
+                let delay = tick_delay(cfg.steps_per_second, game);
 
-                        if new_queue.len() >= 3 {
-                            break 'collect_remaining;
-                        }
-                    },
-                    None => {
-                        break 'collect_remaining;
+                if ! ticker.tick(delay) {
+                    break 'ticking;
+                }
+
+                let directions: Vec<Direction> = controllers.iter_mut().enumerate().map(|(player, controller)| controller.next_direction(game, player)).collect();
+
+                duration += delay;
+                replay.record(&directions);
+                game.tick_all(&directions);
+                ticked = true;
+
+                for (player, controller) in controllers.iter().enumerate() {
+                    if controller.forfeited() {
+                        game.forfeit(player);
                     }
                 }
             }
 
-            queue = new_queue;
+            // drawing is capped at the frame rate, however fast the game ticks
+            let frame_due = last_frame.is_none_or(|last_frame| last_frame.elapsed() >= FRAME_INTERVAL);
+
+            // a resized terminal is laid out anew right away, even between ticks
+            if (ticked && (frame_due || game.is_over())) || resized {
+                display_game(stdout, &mut screen, game, cfg, duration)?;
+                last_frame = Some(Instant::now());
+            }
+        }
+
+        let duration_millis = duration.as_millis() as u64;
 
-            game.tick(&direction);
-            display_game(stdout, &game)?;
+        if let Some(victory) = game.victory() {
+            show_victory(stdout, game, victory, duration_millis, cfg.renderer)?;
+        }
 
-            while now.elapsed() < Duration::from_millis(millis_delay as u64) {}
+        // levels and multiplayer games don't compete for high scores
+        if game.players() == 1 && game.level().is_none() {
+            record_highscore(stdout, cfg, game.snakes()[0].score(), game.snake_len(), duration_millis, game.victory())?;
         }
 
+        let title = if game.players() == 1 {
+            let score = game.snakes()[0].score();
+
+            match game.victory() {
+                Some(_) => format!("YOU WIN  SCORE {}  LENGTH {}", score, game.snake_len()),
+                None => format!("SCORE {}  LENGTH {}", score, game.snake_len())
+            }
+        } else if humans < game.players() {
+            match game.winner() {
+                Some(player) if player < humans => String::from("YOU WIN"),
+                Some(_) => String::from("THE BOT WINS"),
+                None => String::from("DRAW")
+            }
+        } else {
+            match game.winner() {
+                Some(player) => format!("PLAYER {} WINS", player + 1),
+                None => String::from("DRAW")
+            }
+        };
+
         'viewing: loop {
-            match menue(stdout, 32, 8, Some(format!("You got to a length of {}", game.snake_len).as_str()), &["RETRY", "VIEW", "EXIT"])? {
+            match menue(stdout, 40, 13, Some(title.as_str()), &["RETRY", "VIEW", "WATCH REPLAY", "SAVE REPLAY", "EXIT"])? {
                 0 => {
                     game.clear();
                     break 'viewing;
                 },
                 1 => {
-                    display_game(stdout, game)?;
+                    display_game(stdout, &mut Screen::new(), game, cfg, duration)?;
                     wait_for_any_key_press()?;
                 },
+                2 => play_replay(stdout, &replay, cfg)?,
+                3 => save_replay(stdout, &replay)?,
                 _ => break 'retry
             }
         }
     }
 
+    Ok(AfterGame::MainMenu)
+}
+
+const PAUSE_ITEMS: [&str; 5] = ["CONTINUE", "RESTART", "SETTINGS", "MAIN MENU", "QUIT APPLICATION"];
+
+// the pause screen over the dimmed board, returns the index of the chosen
+// entry of `PAUSE_ITEMS`, ESC continues. SETTINGS opens the settings menue
+// for `settings` and comes back here, the changes apply from the next game
+// started in the main menu, RESTART plays with the old ones. A note says so
+// once the settings were opened.
+fn pause(stdout: &mut Stdout, screen: &mut Screen, game: &SnakeGame, cfg: &Config, elapsed: Duration, settings: &mut Config) -> Result<usize> {
+    let mut selected = 0;
+    let mut settings_opened = false;
+
+    loop {
+        screen.begin()?;
+        draw_frame(screen, game, cfg, elapsed)?;
+        screen.dim();
+
+        let mut lines = vec![
+            (String::from(""), Color::White, Color::Black),
+            (String::from("P A U S E D"), Color::Green, Color::Black),
+            (String::from(""), Color::White, Color::Black)
+        ];
+
+        for (i, item) in PAUSE_ITEMS.iter().enumerate() {
+            if i == selected {
+                lines.push((item.to_string(), Color::Black, Color::White));
+            } else {
+                lines.push((item.to_string(), Color::White, Color::Black));
+            }
+        }
+
+        lines.push((String::from(""), Color::White, Color::Black));
+
+        if settings_opened {
+            lines.push((String::from("CHANGED SETTINGS APPLY TO THE"), Color::Grey, Color::Black));
+            lines.push((String::from("NEXT GAME FROM THE MAIN MENU"), Color::Grey, Color::Black));
+            lines.push((String::from(""), Color::White, Color::Black));
+        }
+
+        draw_overlay(screen, &lines, Color::White);
+        screen.present(stdout)?;
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Up => selected = selected.saturating_sub(1),
+                KeyCode::Down if selected < PAUSE_ITEMS.len() - 1 => selected += 1,
+                KeyCode::Esc => return Ok(0),
+                KeyCode::Enter => if selected == 2 {
+                    settings_menue(stdout, settings)?;
+                    screen.invalidate();
+                    settings_opened = true;
+                } else {
+                    return Ok(selected);
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+// counts the resume countdown of `cfg` down over the dimmed board before a
+// paused game goes on, returns false if the terminal lost focus meanwhile
+// and the game should stay paused
+fn countdown(stdout: &mut Stdout, screen: &mut Screen, game: &SnakeGame, cfg: &Config, elapsed: Duration) -> Result<bool> {
+    for n in (1..=cfg.resume_countdown).rev() {
+        let end = Instant::now() + Duration::from_secs(1);
+        let mut redraw = true;
+
+        loop {
+            if redraw {
+                screen.begin()?;
+                draw_frame(screen, game, cfg, elapsed)?;
+                screen.dim();
+
+                draw_overlay(screen, &[
+                    (String::from(""), Color::White, Color::Black),
+                    (n.to_string(), Color::Yellow, Color::Black),
+                    (String::from(""), Color::White, Color::Black)
+                ], Color::Yellow);
+
+                screen.present(stdout)?;
+                redraw = false;
+            }
+
+            if ! poll(end.saturating_duration_since(Instant::now()))? {
+                break;
+            }
+
+            // keys pressed meanwhile don't steer yet
+            match read()? {
+                Event::FocusLost if cfg.pause_on_focus_loss => return Ok(false),
+                Event::Resize(_, _) => redraw = true,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+// draws a box in the middle of the frame with every line centered, each
+// line has its own text, foreground and background color
+fn draw_overlay(screen: &mut Screen, lines: &[(String, Color, Color)], border: Color) {
+    let width = lines.iter().map(|(line, _, _)| line.len()).max().unwrap_or(0) as u16 + 8;
+
+    let (s_width, s_height) = screen.size();
+    let left = s_width.saturating_sub(width) / 2;
+    let top = s_height.saturating_sub(lines.len() as u16 + 2) / 2;
+
+    let bar = "#".repeat(width as usize);
+
+    screen.print(left, top, &bar, border);
+
+    for (i, (line, fg, bg)) in lines.iter().enumerate() {
+        let y = top + 1 + i as u16;
+
+        screen.print(left, y, &format!("#{}#", " ".repeat(width as usize - 2)), border);
+        screen.print_colored(left + (width - line.len() as u16) / 2, y, line, *fg, *bg);
+    }
+
+    screen.print(left, top + 1 + lines.len() as u16, &bar, border);
+}
+
+// the end screen of a won game, the final board with a box on top until a key is pressed
+fn show_victory(stdout: &mut Stdout, game: &SnakeGame, victory: Victory, duration_millis: u64, renderer: Renderer) -> Result<()> {
+    let longest = game.snakes().iter().filter(|snake| ! snake.is_dead()).map(|snake| snake.len()).max().unwrap_or(0);
+    let time = format!("{}:{:02}", duration_millis / 60000, duration_millis / 1000 % 60);
+
+    let reason = match victory {
+        Victory::BoardFilled => String::from("THE BOARD IS FULL"),
+        Victory::Length => format!("LENGTH {} REACHED", longest),
+        Victory::Time => format!("SURVIVED {} SECONDS", duration_millis / 1000)
+    };
+
+    let mut lines = vec![
+        String::from(""),
+        match game.winner() {
+            Some(player) if game.players() > 1 => format!("P L A Y E R   {}   W I N S", player + 1),
+            Some(_) => String::from("V I C T O R Y"),
+            None => String::from("D R A W")
+        },
+        String::from(""),
+        reason
+    ];
+
+    match game.winner() {
+        Some(player) => {
+            let winner = &game.snakes()[player];
+
+            lines.push(format!("SCORE {}  LENGTH {}  TIME {}", winner.score(), winner.len(), time));
+        },
+        // equally long snakes share the victory, all of them are listed
+        None => {
+            for (player, snake) in game.snakes().iter().enumerate() {
+                lines.push(format!("PLAYER {}  SCORE {}  LENGTH {}", player + 1, snake.score(), snake.len()));
+            }
+
+            lines.push(format!("TIME {}", time));
+        }
+    }
+
+    lines.extend([
+        String::from(""),
+        String::from("[ANY KEY]"),
+        String::from("")
+    ]);
+
+    let lines: Vec<(String, Color, Color)> = lines.into_iter().enumerate()
+        .map(|(i, line)| (line, if i == 1 { Color::Yellow } else { Color::White }, Color::Black))
+        .collect();
+
+    let mut screen = Screen::new();
+
+    screen.begin()?;
+    draw_game(&mut screen, game, renderer)?;
+    draw_overlay(&mut screen, &lines, Color::Yellow);
+    screen.present(stdout)?;
+
+    // keys still pressed from steering shouldn't skip the screen right away
+    thread::sleep(Duration::from_millis(500));
+
+    while poll(Duration::from_secs(0))? {
+        read()?;
+    }
+
+    wait_for_any_key_press()
+}
+
+// `cfg` are the settings of the one watching, they decide how it's drawn
+fn play_replay(stdout: &mut Stdout, replay: &Replay, cfg: &Config) -> Result<()> {
+    let mut game = replay.game();
+    let mut tick = 0;
+    let mut speed = 1;
+    let mut paused = false;
+
+    let mut screen = Screen::new();
+
+    'playback: loop {
+        let now = Instant::now();
+        let delay = tick_delay(replay.config.steps_per_second, &game);
+
+        while poll(Duration::from_secs(0))? {
+            if let Event::Key(key_event) = read()? {
+                match key_event.code {
+                    KeyCode::Char(' ') => paused = !paused,
+                    KeyCode::Right => {
+                        paused = true;
+
+                        if replay.step(&mut game, tick) {
+                            tick += 1;
+                        }
+                    },
+                    KeyCode::Left => {
+                        paused = true;
+                        tick = tick.saturating_sub(1);
+                        game = replay.game_at(tick);
+                    },
+                    KeyCode::Up if speed < 64 => speed *= 2,
+                    KeyCode::Down if speed > 1 => speed /= 2,
+                    KeyCode::Esc => break 'playback,
+                    _ => {}
+                }
+            }
+        }
+
+        if ! paused {
+            for _ in 0..speed {
+                if ! replay.step(&mut game, tick) {
+                    paused = true;
+                    break;
+                }
+
+                tick += 1;
+            }
+        }
+
+        screen.begin()?;
+        let viewport = draw_game(&mut screen, &game, cfg.renderer)?;
+
+        if cfg.minimap {
+            draw_minimap(&mut screen, &game, &viewport);
+        }
+
+        let (s_width, s_height) = screen.size();
+
+        let mut status = format!(
+            "TICK {}/{}  SPEED x{}{}  [SPACE] pause [LEFT/RIGHT] step [UP/DOWN] speed [ESC] back",
+            tick,
+            replay.len(),
+            speed,
+            if paused { "  PAUSED" } else { "" }
+        );
+        status.truncate(s_width as usize);
+
+        screen.print(0, s_height.saturating_sub(1), &status, Color::White);
+        screen.present(stdout)?;
+
+        thread::sleep(delay.saturating_sub(now.elapsed()));
+    }
+
     Ok(())
 }
 
+// lets `bot` play game after game until a key is pressed, the attract mode
+fn play_demo(stdout: &mut Stdout, cfg: &Config, name: &str, mut bot: Box<dyn Controller>) -> Result<()> {
+    let tick = Duration::from_secs(1) / cfg.steps_per_second.max(1);
+
+    let cfg = Config {
+        players: 1,
+        ..cfg.clone()
+    };
+
+    let mut screen = Screen::new();
+
+    'demo: loop {
+        let mut game = SnakeGame::from_config(&cfg);
+
+        bot.reset();
+
+        // the finished board stays up for a moment before the next game
+        let mut linger = Duration::from_secs(2).as_nanos() / tick.as_nanos().max(1);
+
+        while linger > 0 {
+            let now = Instant::now();
+            let delay = tick_delay(cfg.steps_per_second, &game);
+
+            if game.is_over() {
+                linger -= 1;
+            } else {
+                let direction = bot.next_direction(&game, 0);
+
+                game.tick(&direction);
+
+                if bot.forfeited() {
+                    game.forfeit(0);
+                }
+            }
+
+            screen.begin()?;
+            let viewport = draw_game(&mut screen, &game, cfg.renderer)?;
+
+            if cfg.minimap {
+                draw_minimap(&mut screen, &game, &viewport);
+            }
+
+            let (s_width, s_height) = screen.size();
+
+            let mut status = format!("DEMO  {}  {}  [ANY KEY] back", name.to_uppercase(), score_line(&game));
+            status.truncate(s_width as usize);
+
+            screen.print(0, s_height.saturating_sub(1), &status, Color::White);
+            screen.present(stdout)?;
+
+            if poll(delay.saturating_sub(now.elapsed()))? {
+                if let Event::Key(_) = read()? {
+                    break 'demo;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// lets the player pick one of the built-in bots, `None` for BACK
+fn choose_bot(stdout: &mut Stdout, title: &str) -> Result<Option<&'static str>> {
+    let names: Vec<String> = bots::NAMES.iter().map(|name| name.to_uppercase()).collect();
+
+    let mut items: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    items.push("BACK");
+
+    let selected = menue(stdout, 80, 20, Some(title), &items)?;
+
+    Ok(bots::NAMES.get(selected).copied())
+}
+
+// the path of `name` next to the settings file
+fn app_path(name: &str) -> io::Result<PathBuf> {
+    let settings = confy::get_configuration_file_path("terminal-snake", Some("settings")).map_err(io::Error::other)?;
+
+    Ok(match settings.parent() {
+        Some(parent) => parent.join(name),
+        None => PathBuf::from(name)
+    })
+}
+
+// a directory next to the settings file, created if it doesn't exist yet
+fn app_dir(name: &str) -> io::Result<PathBuf> {
+    let dir = app_path(name)?;
+
+    fs::create_dir_all(&dir)?;
+
+    Ok(dir)
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0)
+}
+
+fn load_highscores() -> io::Result<HighScores> {
+    HighScores::load(app_path("highscores.json")?)
+}
+
+// asks for the player's name if the game made it into the high score table of `cfg`
+fn record_highscore(stdout: &mut Stdout, cfg: &Config, score: u32, length: u32, duration_millis: u64, victory: Option<Victory>) -> Result<()> {
+    let mut highscores = match load_highscores() {
+        Ok(highscores) => highscores,
+        Err(err) => return notify(stdout, format!("Couldn't load highscores\n{}", err))
+    };
+
+    let mut highscore = HighScore {
+        name: String::from(""),
+        date: unix_secs(),
+        score,
+        length,
+        duration_millis,
+        victory,
+        config: cfg.clone()
+    };
+
+    let rank = match highscores.rank(&highscore) {
+        Some(rank) => rank,
+        None => return Ok(())
+    };
+
+    highscore.name = match request_text(stdout, &format!("#{} HIGHSCORE! NAME", rank + 1))? {
+        Some(name) => name,
+        None => return Ok(())
+    };
+
+    highscores.insert(highscore);
+
+    if let Err(err) = app_path("highscores.json").and_then(|path| highscores.save(path)) {
+        notify(stdout, format!("Couldn't save highscores\n{}", err))?;
+    }
+
+    Ok(())
+}
+
+fn save_replay(stdout: &mut Stdout, replay: &Replay) -> Result<()> {
+    let secs = unix_secs();
+
+    let txt = match app_dir("replays").and_then(|dir| {
+        let path = dir.join(format!("replay-{}.json", secs));
+
+        replay.save(&path).map(|_| path)
+    }) {
+        Ok(path) => format!("Saved replay to\n{}", path.display()),
+        Err(err) => format!("Couldn't save replay\n{}", err)
+    };
+
+    notify(stdout, txt)
+}
+
+// the files in the app directory `name` ending in `extension`, sorted by name
+fn files_in_app_dir(name: &str, extension: &str) -> Vec<PathBuf> {
+    let mut paths = vec![];
+
+    if let Ok(entries) = app_dir(name).and_then(fs::read_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.extension().is_some_and(|ext| ext == extension) {
+                paths.push(path);
+            }
+        }
+    }
+
+    paths.sort();
+
+    paths
+}
+
+fn file_names(paths: &[PathBuf]) -> Vec<String> {
+    paths.iter().map(|path| path.file_stem().unwrap_or_default().to_string_lossy().to_string()).collect()
+}
+
+// lists the most recent replays, newest first
+fn choose_replay(stdout: &mut Stdout, cfg: &Config) -> Result<()> {
+    let mut paths = files_in_app_dir("replays", "json");
+
+    paths.reverse();
+    paths.truncate(10);
+
+    let names = file_names(&paths);
+
+    let mut items: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    items.push("BACK");
+
+    'choosing: loop {
+        let selected = menue(stdout, 80, 20, Some("REPLAYS"), &items)?;
+
+        if selected >= paths.len() {
+            break 'choosing;
+        }
+
+        match Replay::load(&paths[selected]) {
+            Ok(replay) => play_replay(stdout, &replay, cfg)?,
+            Err(err) => notify(stdout, format!("Couldn't load replay\n{}", err))?
+        }
+    }
+
+    Ok(())
+}
+
+// lists the `.level` files in the levels directory to play or edit them
+fn choose_level(stdout: &mut Stdout, cfg: &Config, settings: &mut Config) -> Result<AfterGame> {
+    'choosing: loop {
+        let mut paths = files_in_app_dir("levels", "level");
+        paths.truncate(14);
+
+        let names = file_names(&paths);
+
+        let mut items: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        items.push("NEW LEVEL");
+        items.push("BACK");
+
+        let selected = menue(stdout, 80, 20, Some("LEVELS"), &items)?;
+
+        if selected == paths.len() {
+            new_level(stdout, cfg)?;
+            continue 'choosing;
+        }
+
+        if selected > paths.len() {
+            break 'choosing;
+        }
+
+        let mut level = match Level::load(&paths[selected]) {
+            Ok(level) => level,
+            Err(err) => {
+                notify(stdout, format!("Couldn't load {}\n{}", names[selected], err))?;
+                continue 'choosing;
+            }
+        };
+
+        let title: String = level.name.chars().take(60).collect();
+
+        match menue(stdout, 80, 20, Some(&title), &["PLAY", "EDIT", "BACK"])? {
+            0 => {
+                let mut game = SnakeGame::from_level(&level, cfg);
+
+                if play_game(stdout, &mut game, &level.config(cfg), None, settings)? == AfterGame::Quit {
+                    return Ok(AfterGame::Quit);
+                }
+            },
+            1 => edit_level(stdout, &mut level, &paths[selected])?,
+            _ => {}
+        }
+    }
+
+    Ok(AfterGame::MainMenu)
+}
+
+fn new_level(stdout: &mut Stdout, cfg: &Config) -> Result<()> {
+    let name = match request_text(stdout, "Name")? {
+        Some(name) => name,
+        None => return Ok(())
+    };
+
+    let dir = match app_dir("levels") {
+        Ok(dir) => dir,
+        Err(err) => return notify(stdout, format!("Couldn't create the levels directory\n{}", err))
+    };
+
+    let slug: String = name.to_lowercase().chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+
+    let mut path = dir.join(format!("{}.level", slug));
+    let mut i = 2;
+
+    while path.exists() {
+        path = dir.join(format!("{}-{}.level", slug, i));
+        i += 1;
+    }
+
+    let mut width = cfg.width;
+    let mut height = cfg.height;
+
+    execute!(stdout, Clear(ClearType::All))?;
+    set_size(stdout, &mut width, &mut height)?;
+
+    let mut level = Level::new(&name, width / 2, height);
+
+    edit_level(stdout, &mut level, &path)
+}
+
+// shows `txt` in a box until a key is pressed
+fn notify(stdout: &mut Stdout, txt: String) -> Result<()> {
+    let width = txt.lines().map(|line| line.len()).max().unwrap_or(0) + 6;
+    let height = txt.lines().count() + 4;
+
+    execute!(stdout, Clear(ClearType::All))?;
+    message_box(stdout, width as u16, height as u16, txt)?;
+    wait_for_any_key_press()
+}
+
 fn wait_for_any_key_press() -> Result<()> {
     'wait: loop {
-        match read()? {
-            Event::Key(_) => break 'wait,
-            _ => {}
+        if let Event::Key(_) = read()? {
+            break 'wait;
         }
     }
 
@@ -603,7 +1120,7 @@ fn message_box(stdout: &mut Stdout, width: u16, height: u16, text: String) -> Re
     for (i, line) in lines.iter().enumerate() {
         execute!(
             stdout,
-            MoveTo(margin_left as u16 + if line.len() > width as usize {
+            MoveTo(margin_left + if line.len() > width as usize {
                 0
             } else {
                 (width - line.len() as u16) / 2
@@ -626,16 +1143,15 @@ fn set_size(stdout: &mut Stdout, width: &mut usize, height: &mut usize) -> Resul
     'sellect: loop {
         message_box(stdout, *width as u16, *height as u16, String::from(txt))?;
 
-        match read()? {
-            Event::Key(keyevent) => match keyevent.code {
+        if let Event::Key(keyevent) = read()? {
+            match keyevent.code {
                 KeyCode::Up => *height += 1,
                 KeyCode::Down => *height -= 1,
                 KeyCode::Left => *width -= 1,
                 KeyCode::Right => *width += 1,
                 KeyCode::Enter => break 'sellect,
                 _ => {}
-            },
-            _ => {}
+            }
         }
 
         if *width < 10 {
@@ -657,11 +1173,9 @@ fn request_number(stdout: &mut Stdout) -> Result<u32> {
 
         message_box(stdout, (txt.len() + 6) as u16, 5, txt)?;
 
-        match read()? {
-            Event::Key(key_event) => match key_event.code {
-                KeyCode::Char(c) => if c.is_numeric() {
-                    numb_str.push(c);
-                },
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char(c) if c.is_numeric() => numb_str.push(c),
                 KeyCode::Enter => {
                     match numb_str.parse() {
                         Ok(numb) => return Ok(numb),
@@ -673,20 +1187,53 @@ fn request_number(stdout: &mut Stdout) -> Result<u32> {
                     }
                 },
                 _ => {}
-            },
-            _ => {}
+            }
+        }
+    }
+}
+
+// reads a line of text, `None` if the player pressed ESC
+fn request_text(stdout: &mut Stdout, prompt: &str) -> Result<Option<String>> {
+    let mut text = String::from("");
+
+    execute!(stdout, Clear(ClearType::All))?;
+
+    loop {
+        let txt = format!("{}: {}_", prompt, text);
+
+        message_box(stdout, 40.max(txt.len() + 6) as u16, 5, txt)?;
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Char(c) if text.len() < 30 && (c.is_ascii_alphanumeric() || c == ' ' || c == '-' || c == '_') => text.push(c),
+                KeyCode::Backspace => {
+                    text.pop();
+                },
+                KeyCode::Enter if ! text.trim().is_empty() => return Ok(Some(String::from(text.trim()))),
+                KeyCode::Esc => return Ok(None),
+                _ => {}
+            }
         }
     }
 }
 
-fn set_apple_settings(stdout: &mut Stdout, min_apple_count: &mut u32, max_apple_count: &mut u32, ticks_between_apple_spawn: &mut u32) -> Result<()> {
+fn set_apple_settings(stdout: &mut Stdout, min_apple_count: &mut u32, max_apple_count: &mut u32, ticks_between_apple_spawn: &mut u32, apple_policy: &mut ApplePolicy, min_apple_distance: &mut u32, apple_weights: &mut AppleWeights) -> Result<()> {
     'in_menue: loop {
-        match menue(stdout, 80, 20, Some("Apple Settings"), &[
-            format!("MIN: {}", min_apple_count).as_str(), 
-            format!("MAX: {}", max_apple_count).as_str(),
-            format!("TICKS INBETWEEN SPAWNS: {}", ticks_between_apple_spawn).as_str(),
-            "DONE"
-        ])? {
+        let mut items = vec![
+            format!("MIN: {}", min_apple_count), 
+            format!("MAX: {}", max_apple_count),
+            format!("TICKS INBETWEEN SPAWNS: {}", ticks_between_apple_spawn),
+            format!("SPAWNING: {}", apple_policy.name()),
+            format!("MIN DISTANCE TO HEAD: {}", min_apple_distance)
+        ];
+
+        // one spawn weight per kind of apple
+        items.extend(AppleKind::ALL.iter().map(|kind| format!("{} WEIGHT: {}", kind.name(), apple_weights.get(*kind))));
+        items.push(String::from("DONE"));
+
+        let items: Vec<&str> = items.iter().map(String::as_str).collect();
+
+        match menue(stdout, 80, 20, Some("Apple Settings"), &items)? {
             0 => {
                 *min_apple_count = request_number(stdout)?;
 
@@ -702,6 +1249,9 @@ fn set_apple_settings(stdout: &mut Stdout, min_apple_count: &mut u32, max_apple_
                 }
             },
             2 => *ticks_between_apple_spawn = request_number(stdout)?,
+            3 => *apple_policy = apple_policy.next(),
+            4 => *min_apple_distance = request_number(stdout)?,
+            i if i < 5 + AppleKind::ALL.len() => *apple_weights.get_mut(AppleKind::ALL[i - 5]) = request_number(stdout)?,
             _ => break 'in_menue
         }
     }
@@ -733,15 +1283,28 @@ fn set_snake_settings(stdout: &mut Stdout, game_grow_rate: &mut u32, steps_per_s
     Ok(())
 }
 
-fn set_tweaks(stdout: &mut Stdout, easy: &mut bool, borderless: &mut bool) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+fn set_tweaks(stdout: &mut Stdout, easy: &mut bool, borderless: &mut bool, layout: &mut Layout, resume_countdown: &mut u32, pause_on_focus_loss: &mut bool, pause_on_resize: &mut bool, minimap: &mut bool, renderer: &mut Renderer) -> Result<()> {
     'settings: loop {
         match menue(stdout, 80, 20, Some("Tweaks"), &[
             format!("IMMORTAL: {}", easy).as_str(), 
             format!("BORDERLESS: {}", borderless).as_str(), 
+            format!("LAYOUT: {}", layout.name()).as_str(),
+            format!("RESUME COUNTDOWN: {}", resume_countdown).as_str(),
+            format!("PAUSE ON FOCUS LOSS: {}", pause_on_focus_loss).as_str(),
+            format!("PAUSE ON RESIZE: {}", pause_on_resize).as_str(),
+            format!("MINIMAP: {}", minimap).as_str(),
+            format!("RENDERER: {}", renderer.name()).as_str(),
             "DONE"
         ])? {
             0 => *easy = !*easy,
             1 => *borderless = !*borderless,
+            2 => *layout = layout.next(),
+            3 => *resume_countdown = request_number(stdout)?,
+            4 => *pause_on_focus_loss = !*pause_on_focus_loss,
+            5 => *pause_on_resize = !*pause_on_resize,
+            6 => *minimap = !*minimap,
+            7 => *renderer = renderer.next(),
             _ => break 'settings
         }
     }
@@ -749,36 +1312,151 @@ fn set_tweaks(stdout: &mut Stdout, easy: &mut bool, borderless: &mut bool) -> Re
     Ok(())
 }
 
-#[derive(Serialize, Deserialize)]
-struct Config {
-    width: usize,
-    height: usize,
-    min_apple_count: u32,
-    max_apple_count: u32,
-    ticks_between_apple_spawn: u32,
-    game_grow_rate: u32,
-    steps_per_second: u32,
-    easy: bool,
-    borderless: bool
+// the partial win conditions, filling the board always wins, 0 turns a condition off
+fn set_goals(stdout: &mut Stdout, win_length: &mut Option<u32>, win_seconds: &mut Option<u32>) -> Result<()> {
+    let describe = |value: &Option<u32>| match value {
+        Some(value) => value.to_string(),
+        None => String::from("OFF")
+    };
+
+    'settings: loop {
+        match menue(stdout, 80, 20, Some("Goals"), &[
+            format!("WIN AT LENGTH: {}", describe(win_length)).as_str(),
+            format!("WIN AFTER SECONDS: {}", describe(win_seconds)).as_str(),
+            "DONE"
+        ])? {
+            0 => *win_length = Some(request_number(stdout)?).filter(|length| *length > 0),
+            1 => *win_seconds = Some(request_number(stdout)?).filter(|seconds| *seconds > 0),
+            _ => break 'settings
+        }
+    }
+
+    Ok(())
+}
+
+// prints the levels in the levels directory, the non interactive `levels` command
+fn print_levels() {
+    let paths = files_in_app_dir("levels", "level");
+
+    if paths.is_empty() {
+        match app_dir("levels") {
+            Ok(dir) => println!("no levels in {}", dir.display()),
+            Err(err) => eprintln!("{}", err)
+        }
+    }
+
+    for path in paths {
+        match Level::load(&path) {
+            Ok(level) => println!("{:<30} {:>3}x{:<3} {}", level.name, level.width, level.height, path.display()),
+            Err(err) => println!("{:<30} {} ({})", "-", path.display(), err)
+        }
+    }
+}
+
+// the shortest time a bot program gets to answer while playing, shorter
+// ticks than this would time out every reply
+const MIN_BOT_TIMEOUT: Duration = Duration::from_millis(10);
+
+// the built-in bot called `spec`, otherwise `spec` is the command line of a
+// bot program, its stderr goes to `stderr`
+fn bot_controller(spec: &str, timeout: Duration, policy: FailurePolicy, stderr: Stdio) -> io::Result<Box<dyn Controller>> {
+    if let Some(bot) = bots::bot(spec) {
+        return Ok(bot);
+    }
+
+    let mut parts = spec.split_whitespace();
+
+    let program = match parts.next() {
+        Some(program) => program,
+        None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "the bot command is empty"))
+    };
+
+    let mut command = std::process::Command::new(program);
+    command.args(parts).stderr(stderr);
+
+    Ok(Box::new(ExternalBot::spawn(command, timeout, policy)?))
+}
+
+#[derive(Serialize)]
+struct SimulationSummary<'a> {
+    games: u64,
+    /// games where the last snakes died at once, only with several bots
+    draws: u64,
+    players: Vec<PlayerSummary<'a>>
+}
+
+#[derive(Serialize)]
+struct PlayerSummary<'a> {
+    bot: &'a str,
+    #[serde(flatten)]
+    summary: Summary
+}
+
+// plays `games` games, one snake per bot, and prints the statistics, the `simulate` command
+fn simulate(cfg: &Config, bots: &[String], timeout: Duration, policy: FailurePolicy, games: u64, max_ticks: u64, format: Format) {
+    let mut started: Vec<Box<dyn Controller>> = vec![];
+
+    for spec in bots {
+        match bot_controller(spec, timeout, policy, Stdio::inherit()) {
+            Ok(controller) => started.push(controller),
+            Err(err) => {
+                eprintln!("Couldn't start bot \"{}\": {}", spec, err);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let mut controllers: Vec<&mut dyn Controller> = started.iter_mut().map(|controller| controller.as_mut() as &mut dyn Controller).collect();
+
+    let reports = simulation::play_many(cfg, cfg.seed, &mut controllers, games, max_ticks);
+
+    let summary = SimulationSummary {
+        games,
+        draws: if bots.len() > 1 { reports.iter().filter(|report| report.winner.is_none()).count() as u64 } else { 0 },
+        players: bots.iter().enumerate().map(|(player, bot)| PlayerSummary {
+            bot,
+            summary: Summary::new(&reports, player)
+        }).collect()
+    };
+
+    match format {
+        Format::Json => match serde_json::to_string_pretty(&summary) {
+            Ok(json) => println!("{}", json),
+            Err(err) => eprintln!("{}", err)
+        },
+        Format::Csv => {
+            println!("player,bot,{}", Summary::csv_header());
+
+            for (player, player_summary) in summary.players.iter().enumerate() {
+                println!("{},\"{}\",{}", player, player_summary.bot.replace('"', "\"\""), player_summary.summary.to_csv_row());
+            }
+        }
+    }
 }
 
-impl std::default::Default for Config {
-    fn default() -> Self {
-        Config {
-            width: 80,
-            height: 30,
-            min_apple_count: 1,
-            max_apple_count: 1,
-            ticks_between_apple_spawn: 100,
-            game_grow_rate: 1,
-            steps_per_second: 10,
-            easy: false,
-            borderless: false
+// prints the settings file and the settings of this run, `save` stores them
+fn print_config(cfg: &Config, save: bool) {
+    match confy::get_configuration_file_path("terminal-snake", Some("settings")) {
+        Ok(path) => println!("# {}", path.display()),
+        Err(err) => eprintln!("{}", err)
+    }
+
+    match toml::to_string(cfg) {
+        Ok(txt) => print!("{}", txt),
+        Err(err) => eprintln!("{}", err)
+    }
+
+    if save {
+        match confy::store("terminal-snake", Some("settings"), cfg) {
+            Ok(_) => println!("# saved"),
+            Err(err) => eprintln!("Couldn't save the settings: {}", err)
         }
     }
 }
 
 fn main() -> Result<()> {
+    let cli = Cli::parse();
+
     let mut stdout = stdout();
 
     let mut cfg: Config = match confy::load::<Config>("terminal-snake", Some("settings")) {
@@ -789,45 +1467,179 @@ fn main() -> Result<()> {
         }
     };
 
-    execute!(stdout, EnterAlternateScreen, Hide, SetBackgroundColor(Color::Black), SetForegroundColor(Color::White), SetTitle("Terminal Snake"))?;
+    // the commands that only print something don't need the terminal
+    match &cli.command {
+        Some(Command::Scores { all }) => {
+            match load_highscores() {
+                Ok(highscores) => print_highscores(&highscores, &cli.overrides.apply(&cfg), *all),
+                Err(err) => eprintln!("Couldn't load highscores: {}", err)
+            }
+
+            return Ok(());
+        },
+        Some(Command::Levels) => {
+            print_levels();
+
+            return Ok(());
+        },
+        Some(Command::Simulate { bots, bot_timeout, on_bot_failure, games, max_ticks, format }) => {
+            simulate(&cli.overrides.apply(&cfg), bots, Duration::from_millis(*bot_timeout), on_bot_failure.policy(), *games, *max_ticks, *format);
+
+            return Ok(());
+        },
+        Some(Command::Config { save }) => {
+            print_config(&cli.overrides.apply(&cfg), *save && ! cli.no_save);
+
+            return Ok(());
+        },
+        _ => {}
+    }
+
+    // a bot that doesn't start is reported like in `simulate`, before the
+    // terminal is taken over
+    let bot = match &cli.command {
+        Some(Command::Play { opponent: Some(spec), .. }) | Some(Command::Demo { bot: spec }) => {
+            // a bot program gets one tick to answer, but no less than `MIN_BOT_TIMEOUT`,
+            // its output would mess up the screen
+            let tick = Duration::from_secs(1) / cli.overrides.apply(&cfg).steps_per_second.max(1);
+            let timeout = tick.max(MIN_BOT_TIMEOUT);
+
+            match bot_controller(spec, timeout, FailurePolicy::KeepDirection, Stdio::null()) {
+                Ok(bot) => Some(bot),
+                Err(err) => {
+                    eprintln!("Couldn't start bot \"{}\": {}", spec, err);
+                    std::process::exit(2);
+                }
+            }
+        },
+        _ => None
+    };
+
+    let guard = TerminalGuard::enter()?;
+
+    match &cli.command {
+        Some(Command::Play { level, players, opponent }) => {
+            let play_cfg = Config {
+                players: *players as usize + opponent.iter().count(),
+                ..cli.overrides.apply(&cfg)
+            };
+
+            let after_game = match level {
+                Some(path) => match Level::load(path) {
+                    Ok(level) => play_game(&mut stdout, &mut SnakeGame::from_level(&level, &play_cfg), &level.config(&play_cfg), bot, &mut cfg)?,
+                    Err(err) => {
+                        notify(&mut stdout, format!("Couldn't load {}\n{}", path.display(), err))?;
 
-    enable_raw_mode()?;
+                        AfterGame::Quit
+                    }
+                },
+                None => play_game(&mut stdout, &mut SnakeGame::from_config(&play_cfg), &play_cfg, bot, &mut cfg)?
+            };
+
+            // MAIN MENU goes on in the menu instead of back to the shell
+            if after_game == AfterGame::MainMenu {
+                main_menue(&mut stdout, &mut cfg, &cli.overrides)?;
+            }
+        },
+        Some(Command::Demo { bot: name }) => if let Some(bot) = bot {
+            play_demo(&mut stdout, &cli.overrides.apply(&cfg), name, bot)?
+        },
+        Some(Command::Replay { file: Some(path) }) => match Replay::load(path) {
+            Ok(replay) => play_replay(&mut stdout, &replay, &cli.overrides.apply(&cfg))?,
+            Err(err) => notify(&mut stdout, format!("Couldn't load replay\n{}", err))?
+        },
+        Some(Command::Replay { file: None }) => choose_replay(&mut stdout, &cli.overrides.apply(&cfg))?,
+        _ => main_menue(&mut stdout, &mut cfg, &cli.overrides)?
+    }
+
+    drop(guard);
+
+    if ! cli.no_save {
+        confy::store("terminal-snake", Some("settings"), cfg).unwrap();
+    }
 
+    Ok(())
+}
+
+// the main menue, the settings menues change `cfg` while `overrides` from
+// the command line win over it for every game
+fn main_menue(stdout: &mut Stdout, cfg: &mut Config, overrides: &Overrides) -> Result<()> {
     'application: loop {
         'selection: loop {     
-            let mut game = SnakeGame::create(cfg.width / 2, cfg.height, cfg.game_grow_rate, cfg.max_apple_count, cfg.min_apple_count, cfg.ticks_between_apple_spawn, cfg.easy, cfg.borderless);
+            // the settings for this run, overrides from the command line aren't stored
+            let play_cfg = overrides.apply(cfg);
+
+            let mut game = SnakeGame::from_config(&play_cfg);
             
-            match menue(&mut stdout, 80, 20, Some("T E R M I N A L   S N A K E"), &["PLAY", "SETTINGS", "QUIT"])? {
-                0 => play_game(&mut stdout, &mut game, cfg.steps_per_second)?,
-                1 => loop {
-                    match menue(&mut stdout, 80, 20, Some("SETTINGS"), &[
-                        "SIZE",
-                        "APPLES",
-                        "SNAKE",
-                        "TWEAKS",
-                        "BACK"
-                    ])? {
-                        0 => set_size(&mut stdout, &mut cfg.width, &mut cfg.height)?,
-                        1 => set_apple_settings(&mut stdout, &mut cfg.min_apple_count, &mut cfg.max_apple_count, &mut cfg.ticks_between_apple_spawn)?,
-                        2 => set_snake_settings(&mut stdout, &mut cfg.game_grow_rate, &mut cfg.steps_per_second)?,
-                        3 => set_tweaks(&mut stdout, &mut cfg.easy, &mut cfg.borderless)?,
-                        _ => break 'selection
+            match menue(stdout, 80, 20, Some("T E R M I N A L   S N A K E"), &["PLAY", "2 PLAYERS", "VS BOT", "LEVELS", "REPLAYS", "HIGHSCORES", "DEMO", "SETTINGS", "QUIT"])? {
+                0 => if play_game(stdout, &mut game, &play_cfg, None, cfg)? == AfterGame::Quit {
+                    break 'application;
+                },
+                1 => {
+                    let play_cfg = Config {
+                        players: 2,
+                        ..play_cfg
+                    };
+
+                    if play_game(stdout, &mut SnakeGame::from_config(&play_cfg), &play_cfg, None, cfg)? == AfterGame::Quit {
+                        break 'application;
                     }
-                }
+                },
+                2 => if let Some(name) = choose_bot(stdout, "VS BOT")? {
+                    let play_cfg = Config {
+                        players: 2,
+                        ..play_cfg
+                    };
+
+                    if play_game(stdout, &mut SnakeGame::from_config(&play_cfg), &play_cfg, bots::bot(name), cfg)? == AfterGame::Quit {
+                        break 'application;
+                    }
+                },
+                3 => if choose_level(stdout, &play_cfg, cfg)? == AfterGame::Quit {
+                    break 'application;
+                },
+                4 => choose_replay(stdout, &play_cfg)?,
+                5 => match load_highscores() {
+                    Ok(highscores) => show_highscores(stdout, &highscores, &play_cfg)?,
+                    Err(err) => notify(stdout, format!("Couldn't load highscores\n{}", err))?
+                },
+                6 => if let Some(name) = choose_bot(stdout, "DEMO")? {
+                    if let Some(bot) = bots::bot(name) {
+                        play_demo(stdout, &play_cfg, name, bot)?
+                    }
+                },
+                7 => {
+                    settings_menue(stdout, cfg)?;
+                    break 'selection;
+                },
                 _ => break 'application
             }
         }
     }
 
-    disable_raw_mode()?;
-
-    execute!(stdout, Show, ResetColor, LeaveAlternateScreen)?;
-
-    confy::store("terminal-snake", Some("settings"), cfg).unwrap();
-
     Ok(())
 }
 
+fn settings_menue(stdout: &mut Stdout, cfg: &mut Config) -> Result<()> {
+    loop {
+        match menue(stdout, 80, 20, Some("SETTINGS"), &[
+            "SIZE",
+            "APPLES",
+            "SNAKE",
+            "TWEAKS",
+            "GOALS",
+            "BACK"
+        ])? {
+            0 => set_size(stdout, &mut cfg.width, &mut cfg.height)?,
+            1 => set_apple_settings(stdout, &mut cfg.min_apple_count, &mut cfg.max_apple_count, &mut cfg.ticks_between_apple_spawn, &mut cfg.apple_policy, &mut cfg.min_apple_distance, &mut cfg.apple_weights)?,
+            2 => set_snake_settings(stdout, &mut cfg.game_grow_rate, &mut cfg.steps_per_second)?,
+            3 => set_tweaks(stdout, &mut cfg.easy, &mut cfg.borderless, &mut cfg.layout, &mut cfg.resume_countdown, &mut cfg.pause_on_focus_loss, &mut cfg.pause_on_resize, &mut cfg.minimap, &mut cfg.renderer)?,
+            4 => set_goals(stdout, &mut cfg.win_length, &mut cfg.win_seconds)?,
+            _ => return Ok(())
+        }
+    }
+}
+
 /* 
     TODO
     - all done !!!!
@@ -845,13 +1657,13 @@ fn main() -> Result<()> {
     - some things are settings (size)
     - controll with wasd
     - display length in esc-menue
+    - display mode during play
     - fix curser error on windows
-
-    DISREGARDED IDEAS (these are things that I thought about adding at one point, but currently don't intend to, let me know if you would like them implemented/done)
     - save highscores (specific to mode)
     - look at highscores in the main menue
-    - display mode during play
-    - alternative graphics
+    - alternative graphics (half blocks, braille)
+
+    DISREGARDED IDEAS (these are things that I thought about adding at one point, but currently don't intend to, let me know if you would like them implemented/done)
     - notify if run outside of terminal
     
     - windowed version
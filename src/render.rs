@@ -0,0 +1,184 @@
+use std::io::{
+    Stdout,
+    Write
+};
+
+use crossterm::{
+    queue,
+    cursor::MoveTo,
+    terminal::{
+        size,
+        Clear,
+        ClearType
+    },
+    style::{
+        SetBackgroundColor,
+        SetForegroundColor,
+        Color,
+        Print
+    },
+    Result
+};
+
+use crate::terminal;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    ch: char,
+    fg: Color,
+    bg: Color
+}
+
+const BLANK: Cell = Cell {
+    ch: ' ',
+    fg: Color::White,
+    bg: Color::Black
+};
+
+// A frame buffer the size of the terminal.
+//
+// A frame is drawn into the back buffer between `begin` and `present`,
+// `present` then only sends the cells that differ from the last frame and
+// flushes once. A resize of the terminal, or the terminal being set up
+// again after a suspend, redraws everything.
+pub struct Screen {
+    width: u16,
+    height: u16,
+    front: Vec<Cell>,
+    back: Vec<Cell>,
+    full_redraw: bool,
+    generation: usize
+}
+
+impl Screen {
+    pub fn new() -> Screen {
+        Screen {
+            width: 0,
+            height: 0,
+            front: vec![],
+            back: vec![],
+            full_redraw: true,
+            generation: terminal::generation()
+        }
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    // the terminal was drawn on by something else, the next frame redraws everything
+    pub fn invalidate(&mut self) {
+        self.full_redraw = true;
+    }
+
+    // starts a new, blank frame
+    pub fn begin(&mut self) -> Result<()> {
+        let (width, height) = size()?;
+
+        if (width, height) != (self.width, self.height) {
+            self.width = width;
+            self.height = height;
+            self.front = vec![BLANK; width as usize * height as usize];
+            self.back = self.front.clone();
+            self.full_redraw = true;
+        }
+
+        if terminal::generation() != self.generation {
+            self.generation = terminal::generation();
+            self.full_redraw = true;
+        }
+
+        for cell in &mut self.back {
+            *cell = BLANK;
+        }
+
+        Ok(())
+    }
+
+    // greys out everything drawn so far, to put an overlay on top of it,
+    // backgrounds stay visible since half blocks draw cells with them
+    pub fn dim(&mut self) {
+        for cell in &mut self.back {
+            cell.fg = Color::DarkGrey;
+
+            if cell.bg != Color::Black {
+                cell.bg = Color::DarkGrey;
+            }
+        }
+    }
+
+    // draws `text` onto the frame, anything outside of the terminal is cut off
+    pub fn print(&mut self, x: u16, y: u16, text: &str, fg: Color) {
+        self.print_colored(x, y, text, fg, Color::Black);
+    }
+
+    pub fn print_colored(&mut self, x: u16, y: u16, text: &str, fg: Color, bg: Color) {
+        if y >= self.height {
+            return;
+        }
+
+        for (i, ch) in text.chars().enumerate() {
+            let x = x as usize + i;
+
+            if x >= self.width as usize {
+                break;
+            }
+
+            self.back[y as usize * self.width as usize + x] = Cell { ch, fg, bg };
+        }
+    }
+
+    // sends the changes since the last frame to the terminal
+    pub fn present(&mut self, stdout: &mut Stdout) -> Result<()> {
+        let mut fg = None;
+        let mut bg = None;
+        let mut cursor = None;
+
+        if self.full_redraw {
+            queue!(stdout, SetBackgroundColor(Color::Black), Clear(ClearType::All))?;
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = y as usize * self.width as usize + x as usize;
+                let cell = self.back[i];
+
+                // after a full clear only the non blank cells need drawing
+                let unchanged = if self.full_redraw {
+                    cell == BLANK
+                } else {
+                    cell == self.front[i]
+                };
+
+                if unchanged {
+                    continue;
+                }
+
+                if cursor != Some((x, y)) {
+                    queue!(stdout, MoveTo(x, y))?;
+                }
+
+                if fg != Some(cell.fg) {
+                    queue!(stdout, SetForegroundColor(cell.fg))?;
+                    fg = Some(cell.fg);
+                }
+
+                if bg != Some(cell.bg) {
+                    queue!(stdout, SetBackgroundColor(cell.bg))?;
+                    bg = Some(cell.bg);
+                }
+
+                queue!(stdout, Print(cell.ch))?;
+                cursor = Some((x + 1, y));
+            }
+        }
+
+        queue!(stdout, SetForegroundColor(Color::White), SetBackgroundColor(Color::Black))?;
+        stdout.flush()?;
+
+        std::mem::swap(&mut self.front, &mut self.back);
+        self.full_redraw = false;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    fs,
+    io,
+    path::Path
+};
+
+use serde::{Serialize, Deserialize};
+
+use crate::{
+    Config,
+    Direction,
+    Level,
+    SnakeGame
+};
+
+/// A recorded game, everything needed to play it back tick by tick.
+///
+/// Since apple spawning only depends on the seed, replaying the recorded
+/// directions on a game with the same config and seed reproduces the game.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub config: Config,
+    /// The level the game was played on, if any.
+    #[serde(default)]
+    pub level: Option<Level>,
+    /// The directions passed to [`SnakeGame::tick_all`] on every tick, in order.
+    pub directions: Vec<Vec<Direction>>
+}
+
+impl Replay {
+    /// Starts an empty recording of `game`, which was created from `config`.
+    ///
+    /// Call this before the first tick.
+    pub fn new(config: &Config, game: &SnakeGame) -> Replay {
+        Replay {
+            seed: game.seed(),
+            config: config.clone(),
+            level: game.level().cloned(),
+            directions: vec![]
+        }
+    }
+
+    /// Records the directions of the next tick, one per player.
+    pub fn record(&mut self, directions: &[Direction]) {
+        self.directions.push(directions.to_vec());
+    }
+
+    /// The number of recorded ticks.
+    pub fn len(&self) -> usize {
+        self.directions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directions.is_empty()
+    }
+
+    /// The game as it was before the first tick.
+    pub fn game(&self) -> SnakeGame {
+        let mut game = match &self.level {
+            Some(level) => SnakeGame::from_level(level, &self.config),
+            None => SnakeGame::from_config(&self.config)
+        };
+
+        game.set_seed(self.seed);
+
+        game
+    }
+
+    /// The game as it was after `tick` ticks.
+    pub fn game_at(&self, tick: usize) -> SnakeGame {
+        let mut game = self.game();
+
+        for directions in self.directions.iter().take(tick) {
+            game.tick_all(directions);
+        }
+
+        game
+    }
+
+    /// Advances `game`, which is at `tick`, by the recorded directions of that tick.
+    ///
+    /// Returns `false` when there are no more recorded ticks.
+    pub fn step(&self, game: &mut SnakeGame, tick: usize) -> bool {
+        match self.directions.get(tick) {
+            Some(directions) => {
+                game.tick_all(directions);
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Replay> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
@@ -0,0 +1,221 @@
+use std::io::Stdout;
+
+use crossterm::{
+    event::{
+        Event,
+        KeyCode
+    },
+    style::Color,
+    Result
+};
+
+use terminal_snake::{
+    spawner::{
+        AppleKind,
+        ApplePolicy,
+        AppleWeights
+    },
+    Config,
+    HighScore,
+    HighScores,
+    Layout,
+    Victory
+};
+
+use crate::{
+    render::Screen,
+    terminal::read
+};
+
+const HELP: &str = "[LEFT/RIGHT] other modes [ESC] back";
+
+// shows the high score table of the mode `cfg` belongs to, the other modes
+// that have scores are a LEFT/RIGHT away
+pub fn show_highscores(stdout: &mut Stdout, highscores: &HighScores, cfg: &Config) -> Result<()> {
+    // the current mode comes first, even without any scores
+    let mut tables: Vec<(Config, &[HighScore])> = vec![(cfg.clone(), highscores.get(cfg))];
+
+    for table in highscores.tables() {
+        if table[0].config.mode_hash() != cfg.mode_hash() {
+            tables.push((table[0].config.clone(), table));
+        }
+    }
+
+    let mut selected = 0;
+
+    let mut screen = Screen::new();
+
+    loop {
+        let (mode, scores) = &tables[selected];
+
+        let mut lines = vec![
+            String::from("H I G H S C O R E S"),
+            String::from(""),
+            format!("{}/{}  {}", selected + 1, tables.len(), describe_mode(mode)),
+            String::from(""),
+            format!("    {:<30} {:>7} {:>6} {:>8} {:>6} {:>10}", "NAME", "SCORE", "LENGTH", "TIME", "WON", "DATE")
+        ];
+
+        for (rank, score) in scores.iter().enumerate() {
+            lines.push(format!(
+                "{:>2}. {:<30} {:>7} {:>6} {:>8} {:>6} {:>10}",
+                rank + 1,
+                score.name,
+                score.score,
+                score.length,
+                format_duration(score.duration_millis),
+                format_victory(score.victory),
+                format_date(score.date)
+            ));
+        }
+
+        if scores.is_empty() {
+            lines.push(String::from("no games yet"));
+        }
+
+        screen.begin()?;
+
+        let (s_width, s_height) = screen.size();
+        let top = s_height.saturating_sub(lines.len() as u16) / 2;
+
+        for (i, line) in lines.iter().enumerate() {
+            let x = s_width.saturating_sub(line.len() as u16) / 2;
+
+            screen.print(x, top + i as u16, line, if i == 0 { Color::Green } else { Color::White });
+        }
+
+        screen.print(0, s_height.saturating_sub(1), HELP, Color::White);
+        screen.present(stdout)?;
+
+        if let Event::Key(key_event) = read()? {
+            match key_event.code {
+                KeyCode::Left => selected = (selected + tables.len() - 1) % tables.len(),
+                KeyCode::Right => selected = (selected + 1) % tables.len(),
+                KeyCode::Esc | KeyCode::Enter => break,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// prints the table of the mode `cfg` belongs to, or every table
+pub fn print_highscores(highscores: &HighScores, cfg: &Config, all: bool) {
+    let tables: Vec<&[HighScore]> = if all {
+        highscores.tables().collect()
+    } else {
+        vec![highscores.get(cfg)]
+    };
+
+    if tables.is_empty() {
+        println!("no games yet");
+    }
+
+    for (i, scores) in tables.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        match scores.first() {
+            Some(score) => println!("{}", describe_mode(&score.config)),
+            None => println!("{}\nno games yet", describe_mode(cfg))
+        }
+
+        for (rank, score) in scores.iter().enumerate() {
+            println!(
+                "{:>2}. {:<30} {:>7} {:>6} {:>8} {:>6} {:>10}",
+                rank + 1,
+                score.name,
+                score.score,
+                score.length,
+                format_duration(score.duration_millis),
+                format_victory(score.victory),
+                format_date(score.date)
+            );
+        }
+    }
+}
+
+// the settings a high score table is for, in one line
+fn describe_mode(cfg: &Config) -> String {
+    let mut txt = format!(
+        "{}x{}  APPLES {}-{} EVERY {}  GROW {}  SPEED {}",
+        cfg.width / 2,
+        cfg.height,
+        cfg.min_apple_count,
+        cfg.max_apple_count,
+        cfg.ticks_between_apple_spawn,
+        cfg.game_grow_rate,
+        cfg.steps_per_second
+    );
+
+    if cfg.apple_policy != ApplePolicy::Interval {
+        txt.push_str(&format!("  {}", cfg.apple_policy.name()));
+    }
+
+    if cfg.min_apple_distance > 0 {
+        txt.push_str(&format!("  APPLES {} AWAY", cfg.min_apple_distance));
+    }
+
+    if cfg.apple_weights != AppleWeights::default() {
+        // the weight of every kind that appears at all
+        for kind in AppleKind::ALL.into_iter().filter(|kind| cfg.apple_weights.get(*kind) > 0) {
+            txt.push_str(&format!("  {} {}", kind.name(), cfg.apple_weights.get(kind)));
+        }
+    }
+
+    if cfg.easy {
+        txt.push_str("  IMMORTAL");
+    }
+
+    if cfg.borderless {
+        txt.push_str("  BORDERLESS");
+    }
+
+    if cfg.layout != Layout::Open {
+        txt.push_str(&format!("  {}", cfg.layout.name()));
+    }
+
+    if let Some(win_length) = cfg.win_length {
+        txt.push_str(&format!("  GOAL LENGTH {}", win_length));
+    }
+
+    if let Some(win_seconds) = cfg.win_seconds {
+        txt.push_str(&format!("  GOAL {}", format_duration(win_seconds as u64 * 1000)));
+    }
+
+    txt
+}
+
+// what the game was won by, empty for a lost game
+fn format_victory(victory: Option<Victory>) -> &'static str {
+    match victory {
+        Some(Victory::BoardFilled) => "FULL",
+        Some(Victory::Length) => "LENGTH",
+        Some(Victory::Time) => "TIME",
+        None => ""
+    }
+}
+
+fn format_duration(millis: u64) -> String {
+    let secs = millis / 1000;
+
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+// seconds since the unix epoch as YYYY-MM-DD in UTC
+fn format_date(secs: u64) -> String {
+    // days to civil date, http://howardhinnant.github.io/date_algorithms.html
+    let days = (secs / 86400) as i64 + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,91 @@
+use crate::{
+    spawner::AppleKind,
+    Config
+};
+
+/// How many points an apple is worth, apart from the snake's length.
+///
+/// An apple scores its [base points](AppleKind::points), raised by
+///
+/// - the streak: every apple eaten within [`Scoring::STREAK_TICKS`] ticks of
+///   the one before adds [`Scoring::STREAK_BONUS`] percent, up to
+///   [`Scoring::MAX_STREAK`] apples in a row. A rotten apple ends the streak
+/// - the current speed, see [`SnakeGame::speed_factor`](crate::SnakeGame::speed_factor)
+/// - the multiplier of the settings, 100% for the default ones, see
+///   [`Scoring::from_config`]
+///
+/// ```
+/// use terminal_snake::{scoring::Scoring, spawner::AppleKind, Config};
+///
+/// let scoring = Scoring::from_config(&Config::default());
+///
+/// assert_eq!(scoring.multiplier_percent(), 100);
+/// assert_eq!(scoring.points(AppleKind::Normal, 1, 1.0), 10);
+/// assert_eq!(scoring.points(AppleKind::Golden, 3, 1.0), 60);
+/// assert_eq!(scoring.points(AppleKind::Normal, 1, 1.5), 15);
+///
+/// let fastest = Config { steps_per_second: u32::MAX, borderless: true, ..Config::default() };
+///
+/// assert!(Scoring::from_config(&fastest).multiplier_percent() > 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scoring {
+    multiplier_percent: u32
+}
+
+impl Default for Scoring {
+    /// The scoring of the default settings, without a multiplier.
+    fn default() -> Scoring {
+        Scoring::new(100)
+    }
+}
+
+impl Scoring {
+    /// The most ticks between two apples that still continue a streak.
+    pub const STREAK_TICKS: u64 = 30;
+
+    /// The percentage every apple of a streak after the first adds.
+    pub const STREAK_BONUS: u32 = 10;
+
+    /// The longest streak that still raises the bonus.
+    pub const MAX_STREAK: u32 = 10;
+
+    /// Scoring that multiplies every apple's points by `multiplier_percent` / 100.
+    pub fn new(multiplier_percent: u32) -> Scoring {
+        Scoring {
+            multiplier_percent
+        }
+    }
+
+    /// The scoring of `cfg`: faster snakes score more, 5% more for every
+    /// step per second above the default 10 and 5% less for every one below,
+    /// IMMORTAL snakes score half and BORDERLESS boards a fifth less.
+    pub fn from_config(cfg: &Config) -> Scoring {
+        // saturates instead of overflowing for absurd speeds from the settings file
+        let mut percent = cfg.steps_per_second.max(1).saturating_mul(5).saturating_add(50);
+
+        if cfg.easy {
+            percent /= 2;
+        }
+
+        if cfg.borderless {
+            percent = (percent as u64 * 4 / 5) as u32;
+        }
+
+        Scoring::new(percent.max(1))
+    }
+
+    pub fn multiplier_percent(&self) -> u32 {
+        self.multiplier_percent
+    }
+
+    /// The points of an apple of `kind` eaten as the `streak`th one in a row
+    /// while the game runs `speed_factor` times as fast as configured.
+    pub fn points(&self, kind: AppleKind, streak: u32, speed_factor: f64) -> u32 {
+        let streak_percent = 100 + Scoring::STREAK_BONUS * (streak.clamp(1, Scoring::MAX_STREAK) - 1);
+
+        let points = kind.points() as f64 * streak_percent as f64 / 100.0 * speed_factor * self.multiplier_percent as f64 / 100.0;
+
+        points.round() as u32
+    }
+}
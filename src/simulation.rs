@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+
+use rand::random;
+use serde::Serialize;
+
+use crate::{
+    Config,
+    Controller,
+    Death,
+    SnakeGame,
+    Victory
+};
+
+/// How a single snake did in a simulated game.
+#[derive(Debug, Clone, Serialize)]
+pub struct GameReport {
+    pub seed: u64,
+    pub length: u32,
+    /// See [`Snake::score`](crate::Snake::score).
+    pub score: u32,
+    /// The number of ticks the snake survived.
+    pub ticks: u64,
+    /// What the snake died of, `None` if it was still alive at the end.
+    pub death: Option<Death>,
+    /// See [`Controller::failures`].
+    pub failures: u64
+}
+
+/// How a simulated game with any number of snakes ended.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchReport {
+    pub seed: u64,
+    /// The number of ticks the game lasted.
+    pub ticks: u64,
+    /// See [`SnakeGame::winner`].
+    pub winner: Option<usize>,
+    /// See [`SnakeGame::victory`].
+    pub victory: Option<Victory>,
+    /// One report per player.
+    pub snakes: Vec<GameReport>
+}
+
+/// Plays a one player game of `cfg` started from `seed` without any
+/// terminal, `controller` steers the snake.
+///
+/// The game ends when the snake dies, when it's won or after `max_ticks`.
+/// An IMMORTAL snake plays until it wins or reaches the limit.
+///
+/// ```
+/// use terminal_snake::{bots::Greedy, simulation, Config};
+///
+/// let report = simulation::play(&Config::default(), 1, &mut Greedy, 10_000);
+///
+/// assert!(report.ticks > 0);
+/// assert!(report.length > 1);
+/// ```
+pub fn play(cfg: &Config, seed: u64, controller: &mut dyn Controller, max_ticks: u64) -> GameReport {
+    play_match(cfg, seed, &mut [controller], max_ticks).snakes.remove(0)
+}
+
+/// Plays a game of `cfg` with one snake per controller, started from `seed`.
+///
+/// The game ends once [`SnakeGame::is_over`] or after `max_ticks`. A snake
+/// whose controller [forfeits](Controller::forfeited) is out of the game.
+pub fn play_match(cfg: &Config, seed: u64, controllers: &mut [&mut dyn Controller], max_ticks: u64) -> MatchReport {
+    let mut game = SnakeGame::from_config(&Config {
+        players: controllers.len(),
+        ..cfg.clone()
+    });
+
+    game.set_seed(seed);
+
+    for controller in controllers.iter_mut() {
+        controller.reset();
+    }
+
+    let mut survived = vec![0; controllers.len()];
+
+    while ! game.is_over() && game.ticks() < max_ticks {
+        let mut directions = vec![];
+
+        for (player, controller) in controllers.iter_mut().enumerate() {
+            if game.snakes()[player].is_dead() {
+                directions.push(game.snakes()[player].direction().clone());
+                continue;
+            }
+
+            directions.push(controller.next_direction(&game, player));
+
+            if controller.forfeited() {
+                game.forfeit(player);
+            }
+        }
+
+        if game.is_over() {
+            break;
+        }
+
+        game.tick_all(&directions);
+
+        for (player, snake) in game.snakes().iter().enumerate() {
+            if ! snake.is_dead() {
+                survived[player] = game.ticks();
+            }
+        }
+    }
+
+    MatchReport {
+        seed,
+        ticks: game.ticks(),
+        winner: game.winner(),
+        victory: game.victory(),
+        snakes: game.snakes().iter().enumerate().map(|(player, snake)| GameReport {
+            seed,
+            length: snake.len(),
+            score: snake.score(),
+            ticks: survived[player],
+            death: snake.death(),
+            failures: controllers[player].failures()
+        }).collect()
+    }
+}
+
+/// Plays `games` games with [`play_match`], the seed of game `i` is `seed + i`
+/// or random if `seed` is `None`.
+pub fn play_many(cfg: &Config, seed: Option<u64>, controllers: &mut [&mut dyn Controller], games: u64, max_ticks: u64) -> Vec<MatchReport> {
+    (0..games).map(|i| {
+        let seed = match seed {
+            Some(seed) => seed.wrapping_add(i),
+            None => random()
+        };
+
+        play_match(cfg, seed, controllers, max_ticks)
+    }).collect()
+}
+
+/// Aggregate statistics of one player over many simulated games.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Summary {
+    pub games: u64,
+    pub mean_length: f64,
+    pub max_length: u32,
+    pub mean_score: f64,
+    pub max_score: u32,
+    pub mean_ticks: f64,
+    pub max_ticks: u64,
+    /// How many games ended in each kind of death, see [`Death::name`].
+    /// Snakes still alive at the end count as `survived`.
+    pub deaths: BTreeMap<String, u64>,
+    /// How many games the player won, in one player games the games won by
+    /// a [`Victory`].
+    pub wins: u64,
+    /// The [failures](Controller::failures) of all games together.
+    pub failures: u64
+}
+
+impl Summary {
+    /// The kinds of death in the order [`Summary::to_csv_row`] writes them.
+    pub const DEATHS: [&'static str; 7] = ["border", "wall", "itself", "snake", "head_on", "forfeit", "survived"];
+
+    /// The statistics of `player` in `reports`.
+    pub fn new(reports: &[MatchReport], player: usize) -> Summary {
+        let mut summary = Summary {
+            games: reports.len() as u64,
+            ..Summary::default()
+        };
+
+        for name in Summary::DEATHS {
+            summary.deaths.insert(String::from(name), 0);
+        }
+
+        if reports.is_empty() {
+            return summary;
+        }
+
+        for report in reports {
+            let snake = &report.snakes[player];
+
+            summary.mean_length += snake.length as f64;
+            summary.mean_score += snake.score as f64;
+            summary.mean_ticks += snake.ticks as f64;
+            summary.max_length = summary.max_length.max(snake.length);
+            summary.max_score = summary.max_score.max(snake.score);
+            summary.max_ticks = summary.max_ticks.max(snake.ticks);
+
+            let death = snake.death.map(|death| death.name()).unwrap_or("survived");
+
+            *summary.deaths.entry(String::from(death)).or_default() += 1;
+
+            if report.winner == Some(player) {
+                summary.wins += 1;
+            }
+
+            summary.failures += snake.failures;
+        }
+
+        summary.mean_length /= reports.len() as f64;
+        summary.mean_score /= reports.len() as f64;
+        summary.mean_ticks /= reports.len() as f64;
+
+        summary
+    }
+
+    /// The CSV header matching [`Summary::to_csv_row`].
+    pub fn csv_header() -> String {
+        let mut header = vec!["games", "mean_length", "max_length", "mean_score", "max_score", "mean_ticks", "max_ticks"];
+        header.extend(Summary::DEATHS);
+        header.push("wins");
+        header.push("failures");
+
+        header.join(",")
+    }
+
+    /// The summary as a line of CSV.
+    pub fn to_csv_row(&self) -> String {
+        let mut row = vec![
+            self.games.to_string(),
+            format!("{:.3}", self.mean_length),
+            self.max_length.to_string(),
+            format!("{:.3}", self.mean_score),
+            self.max_score.to_string(),
+            format!("{:.3}", self.mean_ticks),
+            self.max_ticks.to_string()
+        ];
+
+        for name in Summary::DEATHS {
+            row.push(self.deaths.get(name).copied().unwrap_or(0).to_string());
+        }
+
+        row.push(self.wins.to_string());
+        row.push(self.failures.to_string());
+
+        row.join(",")
+    }
+}
@@ -0,0 +1,322 @@
+use std::str::FromStr;
+
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+
+use crate::Config;
+
+/// The kinds of apples and what eating them does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AppleKind {
+    /// Grows the snake by the grow rate.
+    Normal,
+    /// Grows the snake like a normal apple and is worth more points.
+    Golden,
+    /// Shrinks the snake by the grow rate instead, but never below its head.
+    Rotten,
+    /// Grows the snake like a normal apple and speeds the game up for
+    /// [`SnakeGame::SPEED_BOOST_TICKS`](crate::SnakeGame::SPEED_BOOST_TICKS) ticks.
+    Speed,
+    /// Grows the snake like a normal apple, but rots away after
+    /// [`SnakeGame::SHORT_LIVED_TICKS`](crate::SnakeGame::SHORT_LIVED_TICKS) ticks.
+    ShortLived
+}
+
+impl AppleKind {
+    pub const ALL: [AppleKind; 5] = [AppleKind::Normal, AppleKind::Golden, AppleKind::Rotten, AppleKind::Speed, AppleKind::ShortLived];
+
+    /// The name shown in the settings menu.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppleKind::Normal => "NORMAL",
+            AppleKind::Golden => "GOLDEN",
+            AppleKind::Rotten => "ROTTEN",
+            AppleKind::Speed => "SPEED",
+            AppleKind::ShortLived => "SHORT-LIVED"
+        }
+    }
+
+    /// The points eating the apple is worth before any bonus, see
+    /// [`Scoring`](crate::scoring::Scoring).
+    pub fn points(&self) -> u32 {
+        match self {
+            AppleKind::Normal | AppleKind::Speed => 10,
+            AppleKind::Golden => 50,
+            AppleKind::Rotten => 0,
+            AppleKind::ShortLived => 20
+        }
+    }
+}
+
+/// How often each kind of apple appears compared to the others, an apple
+/// of a kind with weight 0 never appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppleWeights {
+    pub normal: u32,
+    pub golden: u32,
+    pub rotten: u32,
+    pub speed: u32,
+    pub short_lived: u32
+}
+
+impl Default for AppleWeights {
+    /// Only normal apples.
+    fn default() -> AppleWeights {
+        AppleWeights {
+            normal: 1,
+            golden: 0,
+            rotten: 0,
+            speed: 0,
+            short_lived: 0
+        }
+    }
+}
+
+impl AppleWeights {
+    pub fn get(&self, kind: AppleKind) -> u32 {
+        match kind {
+            AppleKind::Normal => self.normal,
+            AppleKind::Golden => self.golden,
+            AppleKind::Rotten => self.rotten,
+            AppleKind::Speed => self.speed,
+            AppleKind::ShortLived => self.short_lived
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: AppleKind) -> &mut u32 {
+        match kind {
+            AppleKind::Normal => &mut self.normal,
+            AppleKind::Golden => &mut self.golden,
+            AppleKind::Rotten => &mut self.rotten,
+            AppleKind::Speed => &mut self.speed,
+            AppleKind::ShortLived => &mut self.short_lived
+        }
+    }
+
+    /// A random kind by weight, normal apples if every weight is 0.
+    ///
+    /// `rng` is only used when more than one kind can appear, so games with
+    /// only normal apples play out as they did before there were kinds.
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha8Rng;
+    /// use terminal_snake::spawner::{AppleKind, AppleWeights};
+    ///
+    /// let mut rng = ChaCha8Rng::seed_from_u64(0);
+    /// let weights = AppleWeights { normal: 0, golden: 1, ..AppleWeights::default() };
+    ///
+    /// assert!((0..100).all(|_| weights.choose(&mut rng) == AppleKind::Golden));
+    /// ```
+    pub fn choose<R: Rng>(&self, rng: &mut R) -> AppleKind {
+        // the weights may add up to more than fits into a u32
+        let total: u64 = AppleKind::ALL.iter().map(|kind| u64::from(self.get(*kind))).sum();
+
+        let mut possible = AppleKind::ALL.into_iter().filter(|kind| self.get(*kind) > 0);
+
+        match (possible.next(), possible.next()) {
+            (None, _) => return AppleKind::Normal,
+            (Some(kind), None) => return kind,
+            _ => {}
+        }
+
+        // drawn as a u32 whenever it fits, so seeded games play out as before
+        let mut pick = match u32::try_from(total) {
+            Ok(total) => u64::from(rng.gen_range(0..total)),
+            Err(_) => rng.gen_range(0..total)
+        };
+
+        for kind in AppleKind::ALL {
+            let weight = u64::from(self.get(kind));
+
+            if pick < weight {
+                return kind;
+            }
+
+            pick -= weight;
+        }
+
+        AppleKind::Normal
+    }
+}
+
+/// When new apples appear, besides the ones that keep the board at its
+/// minimum, see [`AppleSpawner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ApplePolicy {
+    /// One apple once more than the interval passed since the last one.
+    #[default]
+    Interval,
+    /// Like [`ApplePolicy::Interval`], but every wait is drawn anew between
+    /// none and twice the interval, so on average it's the interval.
+    RandomInterval,
+    /// The board is topped up to the maximum every tick.
+    KeepMax
+}
+
+impl ApplePolicy {
+    pub const ALL: [ApplePolicy; 3] = [ApplePolicy::Interval, ApplePolicy::RandomInterval, ApplePolicy::KeepMax];
+
+    /// The name shown in the settings menu.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ApplePolicy::Interval => "INTERVAL",
+            ApplePolicy::RandomInterval => "RANDOM INTERVAL",
+            ApplePolicy::KeepMax => "KEEP MAX"
+        }
+    }
+
+    /// The policy after this one, wrapping around.
+    pub fn next(&self) -> ApplePolicy {
+        let i = ApplePolicy::ALL.iter().position(|policy| policy == self).unwrap_or(0);
+
+        ApplePolicy::ALL[(i + 1) % ApplePolicy::ALL.len()]
+    }
+}
+
+/// Parses [`ApplePolicy::name`], ignoring case, with `-` or `_` for spaces.
+impl FromStr for ApplePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ApplePolicy, String> {
+        let name = s.replace(['-', '_'], " ");
+
+        ApplePolicy::ALL.into_iter()
+            .find(|policy| policy.name().eq_ignore_ascii_case(&name))
+            .ok_or_else(|| format!("unknown apple policy \"{}\", expected interval, random-interval or keep-max", s))
+    }
+}
+
+/// Decides how many apples appear on each tick and how far from the snakes'
+/// heads they have to be.
+///
+/// The rules, applied after every tick:
+///
+/// - the board never gets more than `max_apple_count` apples from spawning,
+///   and nothing happens while it has that many, not even the interval
+///   counting on
+/// - below `min_apple_count` apples it's topped up to the minimum at once
+/// - otherwise the [`ApplePolicy`] decides, the interval counts the ticks
+///   since the last apple appeared for any reason
+///
+/// Every apple goes onto a uniformly random free cell, among the ones at
+/// least `min_head_distance` steps from every living snake's head if there
+/// are any. Its kind is drawn by the [`AppleWeights`].
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use terminal_snake::spawner::{ApplePolicy, AppleSpawner};
+///
+/// let mut rng = ChaCha8Rng::seed_from_u64(0);
+/// let mut spawner = AppleSpawner::new(ApplePolicy::Interval, 2, 1, 3, 0);
+///
+/// // below the minimum
+/// assert_eq!(spawner.due(0, &mut rng), 1);
+///
+/// // the next one once more than 2 ticks passed
+/// assert_eq!((0..3).map(|_| spawner.due(1, &mut rng)).collect::<Vec<_>>(), [0, 0, 1]);
+///
+/// // never more than the maximum
+/// assert_eq!((0..5).map(|_| spawner.due(3, &mut rng)).sum::<u32>(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AppleSpawner {
+    policy: ApplePolicy,
+    interval: u32,
+    min_apple_count: u32,
+    max_apple_count: u32,
+    min_head_distance: usize,
+    weights: AppleWeights,
+    ticks_since_last_spawn: u32,
+    // the wait before the next apple, drawn anew for `RandomInterval`
+    wait: u32
+}
+
+impl AppleSpawner {
+    /// A spawner waiting `interval` ticks between apples and keeping between
+    /// `min_apple_count` and `max_apple_count` apples on the board, a larger
+    /// minimum raises the maximum.
+    pub fn new(policy: ApplePolicy, interval: u32, min_apple_count: u32, max_apple_count: u32, min_head_distance: usize) -> AppleSpawner {
+        AppleSpawner {
+            policy,
+            interval,
+            min_apple_count,
+            max_apple_count: max_apple_count.max(min_apple_count),
+            min_head_distance,
+            weights: AppleWeights::default(),
+            ticks_since_last_spawn: 0,
+            wait: interval
+        }
+    }
+
+    /// The spawner for the apple settings of `cfg`.
+    pub fn from_config(cfg: &Config) -> AppleSpawner {
+        let mut spawner = AppleSpawner::new(cfg.apple_policy, cfg.ticks_between_apple_spawn, cfg.min_apple_count, cfg.max_apple_count, cfg.min_apple_distance as usize);
+        spawner.set_weights(cfg.apple_weights);
+
+        spawner
+    }
+
+    /// Replaces how often each kind of apple appears, only normal ones by default.
+    pub fn set_weights(&mut self, weights: AppleWeights) {
+        self.weights = weights;
+    }
+
+    pub fn weights(&self) -> AppleWeights {
+        self.weights
+    }
+
+    pub fn policy(&self) -> ApplePolicy {
+        self.policy
+    }
+
+    pub fn min_apple_count(&self) -> u32 {
+        self.min_apple_count
+    }
+
+    pub fn max_apple_count(&self) -> u32 {
+        self.max_apple_count
+    }
+
+    /// The fewest steps between a new apple and any snake's head.
+    pub fn min_head_distance(&self) -> usize {
+        self.min_head_distance
+    }
+
+    /// Forgets the time since the last apple, for a new game.
+    pub fn reset(&mut self) {
+        self.ticks_since_last_spawn = 0;
+        self.wait = self.interval;
+    }
+
+    /// Counts a tick with `apples` apples on the board and returns how many
+    /// should appear now.
+    ///
+    /// Only [`ApplePolicy::RandomInterval`] uses `rng`.
+    pub fn due<R: Rng>(&mut self, apples: u32, rng: &mut R) -> u32 {
+        if apples >= self.max_apple_count {
+            return 0;
+        }
+
+        self.ticks_since_last_spawn += 1;
+
+        let by_policy = match self.policy {
+            ApplePolicy::Interval | ApplePolicy::RandomInterval => (self.ticks_since_last_spawn > self.wait) as u32,
+            ApplePolicy::KeepMax => self.max_apple_count - apples
+        };
+
+        let count = by_policy.max(self.min_apple_count.saturating_sub(apples));
+
+        if count > 0 {
+            self.ticks_since_last_spawn = 0;
+
+            if self.policy == ApplePolicy::RandomInterval {
+                self.wait = rng.gen_range(0..=self.interval.saturating_mul(2));
+            }
+        }
+
+        count
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    io::stdout,
+    panic,
+    sync::atomic::{
+        AtomicBool,
+        AtomicUsize,
+        Ordering
+    }
+};
+
+use crossterm::{
+    execute,
+    cursor::{
+        Hide,
+        Show
+    },
+    terminal::{
+        enable_raw_mode,
+        disable_raw_mode,
+        EnterAlternateScreen,
+        LeaveAlternateScreen,
+        SetTitle,
+        size
+    },
+    style::{
+        SetBackgroundColor,
+        SetForegroundColor,
+        ResetColor,
+        Color
+    },
+    event::{
+        self,
+        DisableFocusChange,
+        EnableFocusChange,
+        Event,
+        KeyCode,
+        KeyModifiers
+    },
+    Result
+};
+
+// whether the terminal is currently set up for the game
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+// counts how often the terminal was set up again after a suspend, see `generation`
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+// Puts the terminal into raw mode on the alternate screen and restores it
+// when dropped.
+//
+// The terminal is also restored before a panic message is printed and when
+// the process is terminated, hung up on or suspended with Ctrl+Z.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter() -> Result<TerminalGuard> {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            default_hook(info);
+        }));
+
+        #[cfg(unix)]
+        signals::spawn_handler()?;
+
+        ACTIVE.store(true, Ordering::SeqCst);
+        setup()?;
+
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+// the caller marks the terminal as `ACTIVE` first, so it's restored even
+// if this fails halfway
+fn setup() -> Result<()> {
+    execute!(stdout(), EnterAlternateScreen, Hide, SetBackgroundColor(Color::Black), SetForegroundColor(Color::White), SetTitle("Terminal Snake"), EnableFocusChange)?;
+
+    enable_raw_mode()
+}
+
+// gives the terminal back the way it was, does nothing if it already is
+fn restore() {
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout(), DisableFocusChange, Show, ResetColor, LeaveAlternateScreen);
+    }
+}
+
+// sets the terminal up again after the process was continued
+//
+// both the main thread and the signal handler thread resume, only the one
+// that claims the flag sets the terminal up
+fn resume() {
+    if ACTIVE.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+        return;
+    }
+
+    if setup().is_ok() {
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+    } else {
+        ACTIVE.store(false, Ordering::SeqCst);
+    }
+}
+
+// changes whenever the terminal was set up again, everything drawn before is gone then
+pub fn generation() -> usize {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+// `crossterm::event::read`, except that Ctrl+Z suspends the game
+//
+// raw mode turns Ctrl+Z into a key press instead of SIGTSTP, after the
+// shell continues the game this returns a resize event so the caller redraws
+pub fn read() -> Result<Event> {
+    match event::read()? {
+        Event::Key(key_event) if key_event.code == KeyCode::Char('z') && key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            suspend();
+
+            let (width, height) = size()?;
+
+            Ok(Event::Resize(width, height))
+        },
+        event => Ok(event)
+    }
+}
+
+#[cfg(unix)]
+fn suspend() {
+    restore();
+    signals::stop();
+    resume();
+}
+
+// there is no job control to give the terminal back to
+#[cfg(not(unix))]
+fn suspend() {}
+
+#[cfg(unix)]
+mod signals {
+    use std::{
+        io,
+        process,
+        thread
+    };
+
+    use signal_hook::{
+        consts::signal::{
+            SIGCONT,
+            SIGHUP,
+            SIGINT,
+            SIGTERM,
+            SIGTSTP
+        },
+        iterator::Signals,
+        low_level::emulate_default_handler
+    };
+
+    // restores the terminal on signals that end or stop the process
+    pub fn spawn_handler() -> io::Result<()> {
+        let mut signals = Signals::new([SIGTERM, SIGHUP, SIGINT, SIGTSTP, SIGCONT])?;
+
+        thread::spawn(move || {
+            for signal in signals.forever() {
+                match signal {
+                    SIGTSTP => {
+                        super::restore();
+                        stop();
+                    },
+                    SIGCONT => super::resume(),
+                    _ => {
+                        super::restore();
+                        process::exit(128 + signal);
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    // stops the process like an unhandled SIGTSTP would, returns once it's continued
+    pub fn stop() {
+        let _ = emulate_default_handler(SIGTSTP);
+    }
+}
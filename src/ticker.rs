@@ -0,0 +1,156 @@
+use std::time::{
+    Duration,
+    Instant
+};
+
+/// Where a [`Ticker`] gets the time from, so tests can step it by hand.
+pub trait Clock {
+    /// The time passed since some fixed point, it must never go back.
+    fn now(&self) -> Duration;
+}
+
+/// The real time since the clock was created.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock {
+            start: Instant::now()
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> SystemClock {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl<C: Clock> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+/// A fixed timestep scheduler, it decides when the next tick of a game is due
+/// independent of how long drawing or anything else takes.
+///
+/// Every tick is scheduled one interval after the one before, not after the
+/// moment it ran, so the rate stays steady even when single ticks run late.
+/// A ticker that falls more than [`Ticker::MAX_LAG`] behind gives up on the
+/// missed ticks instead of rushing through them.
+///
+/// Ticks that take longer than their interval, like a slow bot on a large
+/// board, can't be caught up on at all. After [`Ticker::MAX_BURST`] of
+/// ticking without a break the ticker says no tick is due and starts over
+/// from there, so the front-end still reads input and draws in between.
+///
+/// The front-end waits [`Ticker::until_next`] for input, then runs the game
+/// for as long as [`Ticker::tick`] says a tick is due and draws once, which
+/// also works for intervals shorter than the wait can be.
+///
+/// ```
+/// use std::{cell::Cell, time::Duration};
+/// use terminal_snake::ticker::{Clock, Ticker};
+///
+/// struct Manual(Cell<Duration>);
+///
+/// impl Clock for Manual {
+///     fn now(&self) -> Duration {
+///         self.0.get()
+///     }
+/// }
+///
+/// let clock = Manual(Cell::new(Duration::ZERO));
+/// let mut ticker = Ticker::new(&clock);
+/// let interval = Duration::from_millis(100);
+///
+/// assert!(ticker.tick(interval));
+/// assert!(! ticker.tick(interval));
+/// assert_eq!(ticker.until_next(), interval);
+///
+/// clock.0.set(Duration::from_millis(250));
+///
+/// // the ticks due at 100 and 200 milliseconds
+/// assert_eq!((0..5).filter(|_| ticker.tick(interval)).count(), 2);
+/// assert_eq!(ticker.until_next(), Duration::from_millis(50));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ticker<C: Clock> {
+    clock: C,
+    // when the next tick is due, by the clock
+    next: Duration,
+    // when the ticks due one after another without a break started
+    burst_start: Option<Duration>
+}
+
+impl<C: Clock> Ticker<C> {
+    /// How far a ticker may fall behind before it skips the missed ticks.
+    pub const MAX_LAG: Duration = Duration::from_millis(250);
+
+    /// How long ticks may run one after another before the caller gets a break.
+    pub const MAX_BURST: Duration = Duration::from_millis(50);
+
+    /// A ticker whose first tick is due right away.
+    pub fn new(clock: C) -> Ticker<C> {
+        let next = clock.now();
+
+        Ticker {
+            clock,
+            next,
+            burst_start: None
+        }
+    }
+
+    /// Whether a tick is due now. If so it counts as done and the next one is
+    /// due `interval` after it, the interval may change from tick to tick.
+    ///
+    /// Once ticks were due for longer than [`Ticker::MAX_BURST`] in a row
+    /// this is false and the next tick is due `interval` from now.
+    pub fn tick(&mut self, interval: Duration) -> bool {
+        let now = self.clock.now();
+
+        if now < self.next {
+            self.burst_start = None;
+            return false;
+        }
+
+        let burst_start = *self.burst_start.get_or_insert(now);
+
+        if now - burst_start > Ticker::<C>::MAX_BURST {
+            self.burst_start = None;
+            self.next = now + interval;
+
+            return false;
+        }
+
+        if now - self.next > Ticker::<C>::MAX_LAG {
+            self.next = now;
+        }
+
+        self.next += interval;
+
+        true
+    }
+
+    /// How long until the next tick is due, zero if it's due already.
+    pub fn until_next(&self) -> Duration {
+        self.next.saturating_sub(self.clock.now())
+    }
+
+    /// Makes the next tick due `interval` from now, for example after a
+    /// pause, so the time spent paused isn't caught up on.
+    pub fn resync(&mut self, interval: Duration) {
+        self.burst_start = None;
+        self.next = self.clock.now() + interval;
+    }
+}
@@ -0,0 +1,84 @@
+use terminal_snake::{
+    layout::Layout,
+    spawner::{
+        ApplePolicy,
+        AppleWeights
+    },
+    Config,
+    Direction,
+    SnakeGame,
+    SnakeGameCord
+};
+
+// a game that runs for a while and uses the rng for apple places, kinds,
+// intervals and pillars
+fn config(seed: u64) -> Config {
+    Config {
+        easy: true,
+        borderless: true,
+        layout: Layout::Pillars,
+        apple_policy: ApplePolicy::RandomInterval,
+        max_apple_count: 8,
+        apple_weights: AppleWeights {
+            normal: 4,
+            golden: 1,
+            rotten: 1,
+            speed: 1,
+            short_lived: 1
+        },
+        seed: Some(seed),
+        ..Config::default()
+    }
+}
+
+// the same turns for every game, whatever the board looks like
+fn input(tick: u64) -> Direction {
+    Direction::ALL[((tick / 5 + tick / 3) % 4) as usize].clone()
+}
+
+// the apples of every tick, as long as the game lasts but at most `ticks`
+fn apples_per_tick(seed: u64, ticks: u64) -> Vec<Vec<SnakeGameCord>> {
+    let mut game = SnakeGame::from_config(&config(seed));
+    let mut apples = vec![game.apples().collect()];
+
+    for tick in 0..ticks {
+        if game.is_over() {
+            break;
+        }
+
+        // turning back would be a crash, the snake keeps going instead
+        let direction = match input(tick) {
+            direction if direction == game.snakes()[0].direction().opposite() => game.snakes()[0].direction().clone(),
+            direction => direction
+        };
+
+        game.tick(&direction);
+
+        apples.push(game.apples().collect());
+    }
+
+    apples
+}
+
+#[test]
+fn the_same_seed_places_the_same_apples_every_tick() {
+    for seed in [0, 1, 42, u64::MAX] {
+        let first = apples_per_tick(seed, 500);
+        let second = apples_per_tick(seed, 500);
+
+        assert!(first.len() > 100, "the game with seed {} ended after {} ticks", seed, first.len());
+        assert_eq!(first.len(), second.len());
+
+        for (tick, (first, second)) in first.iter().zip(&second).enumerate() {
+            assert_eq!(first, second, "the apples differ on tick {} with seed {}", tick, seed);
+        }
+    }
+}
+
+#[test]
+fn a_different_seed_places_different_apples() {
+    let first = apples_per_tick(1, 500);
+    let second = apples_per_tick(2, 500);
+
+    assert_ne!(first, second);
+}
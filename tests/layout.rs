@@ -0,0 +1,33 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use terminal_snake::layout::Layout;
+
+#[test]
+fn the_start_never_faces_a_wall() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+    for layout in Layout::ALL {
+        for width in [4, 5, 7, 10, 23, 40, 80] {
+            for height in 2..=40 {
+                let start = layout.start(width, height);
+                let walls = layout.walls(width, height, &mut rng);
+
+                // pillars only keep a few cells in front of the start clear
+                let ahead = match layout {
+                    Layout::Pillars => start.x + 8,
+                    _ => width - 1
+                };
+
+                assert!(start.x < width && start.y < height);
+
+                for x in start.x..=ahead.min(width - 1) {
+                    assert!(
+                        ! walls.iter().any(|wall| wall.x == x && wall.y == start.y),
+                        "{:?} on {}x{} has a wall at {}, {} in front of the start {:?}", layout, width, height, x, start.y, start
+                    );
+                }
+            }
+        }
+    }
+}
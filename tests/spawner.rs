@@ -0,0 +1,217 @@
+use proptest::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use terminal_snake::{
+    spawner::{
+        AppleKind,
+        ApplePolicy,
+        AppleSpawner,
+        AppleWeights
+    },
+    Config,
+    Direction,
+    SnakeGame,
+    SnakeGameCord,
+    Tile
+};
+
+fn policy() -> impl Strategy<Value = ApplePolicy> {
+    prop::sample::select(ApplePolicy::ALL.to_vec())
+}
+
+// the cell of the first apple of a game started from `seed`, and the cells it could have been on
+fn first_apple(cfg: &Config, seed: u64) -> (SnakeGameCord, Vec<SnakeGameCord>) {
+    let mut game = SnakeGame::from_config(cfg);
+    game.set_seed(seed);
+    game.tick(&Direction::Right);
+
+    let apples: Vec<SnakeGameCord> = game.apples().collect();
+    assert_eq!(apples.len(), 1);
+
+    let head = game.snake_head_pos().clone();
+
+    let candidates = (0..game.height())
+        .flat_map(|y| (0..game.width()).map(move |x| SnakeGameCord { x, y }))
+        .filter(|pos| game.data()[pos.y][pos.x] != Tile::Snake(0))
+        .filter(|pos| game.distance(&head, pos) >= cfg.min_apple_distance as usize)
+        .collect();
+
+    (apples[0].clone(), candidates)
+}
+
+// Pearson's chi-squared statistic of `counts` against a uniform distribution
+fn chi_squared(counts: &[u32]) -> f64 {
+    let total: u32 = counts.iter().sum();
+    let expected = total as f64 / counts.len() as f64;
+
+    counts.iter().map(|count| (*count as f64 - expected).powi(2) / expected).sum()
+}
+
+// far above the 99.99th percentile of the chi-squared distribution with `df` degrees of freedom
+fn critical_value(df: f64) -> f64 {
+    df + 6.0 * (2.0 * df).sqrt()
+}
+
+proptest! {
+    #[test]
+    fn due_keeps_the_count_between_min_and_max(
+        policy in policy(),
+        interval in 0u32..20,
+        min in 0u32..5,
+        max in 0u32..10,
+        seed: u64,
+        apples in prop::collection::vec(0u32..12, 1..200)
+    ) {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut spawner = AppleSpawner::new(policy, interval, min, max, 0);
+        let max = spawner.max_apple_count();
+
+        for apples in apples {
+            let due = spawner.due(apples, &mut rng);
+
+            if apples >= max {
+                prop_assert_eq!(due, 0);
+            } else {
+                prop_assert!(apples + due <= max);
+                prop_assert!(apples + due >= min);
+            }
+        }
+    }
+
+    #[test]
+    fn intervals_are_kept(policy in policy(), interval in 0u32..30, seed: u64) {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        // without a minimum and with room for more apples only the policy spawns
+        let mut spawner = AppleSpawner::new(policy, interval, 0, 2, 0);
+
+        let mut waits = vec![];
+        let mut wait = 0;
+
+        for _ in 0..1000 {
+            wait += 1;
+
+            if spawner.due(1, &mut rng) > 0 {
+                waits.push(wait);
+                wait = 0;
+            }
+        }
+
+        for wait in waits {
+            match policy {
+                ApplePolicy::Interval => prop_assert_eq!(wait, interval + 1),
+                ApplePolicy::RandomInterval => prop_assert!(wait >= 1 && wait <= interval * 2 + 1),
+                ApplePolicy::KeepMax => prop_assert_eq!(wait, 1)
+            }
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(12))]
+
+    #[test]
+    fn apples_are_placed_uniformly(width in 3usize..8, height in 2usize..7, min_apple_distance in 0u32..4, base: u64) {
+        let cfg = Config {
+            width: width * 2,
+            height,
+            min_apple_distance,
+            ..Config::default()
+        };
+
+        let (_, candidates) = first_apple(&cfg, base);
+        prop_assume!(candidates.len() > 1);
+
+        let mut counts = vec![0; candidates.len()];
+
+        for i in 0..candidates.len() as u64 * 100 {
+            let (apple, _) = first_apple(&cfg, base.wrapping_add(i));
+
+            match candidates.iter().position(|candidate| *candidate == apple) {
+                Some(i) => counts[i] += 1,
+                None => prop_assert!(false, "apple at {:?} isn't a candidate", apple)
+            }
+        }
+
+        let statistic = chi_squared(&counts);
+
+        prop_assert!(statistic < critical_value(counts.len() as f64 - 1.0), "chi squared {} for {:?}", statistic, counts);
+    }
+
+    #[test]
+    fn apples_keep_their_distance_from_the_head(min_apple_distance in 1u32..5, seed: u64, turns in prop::collection::vec(0usize..4, 50)) {
+        let cfg = Config {
+            width: 40,
+            height: 20,
+            min_apple_count: 3,
+            max_apple_count: 3,
+            min_apple_distance,
+            ..Config::default()
+        };
+
+        let mut game = SnakeGame::from_config(&cfg);
+        game.set_seed(seed);
+
+        for turn in turns {
+            let before: Vec<SnakeGameCord> = game.apples().collect();
+
+            let direction = Direction::ALL[turn].clone();
+
+            if direction == game.snakes()[0].direction().opposite() {
+                continue;
+            }
+
+            game.tick(&direction);
+
+            if game.is_over() {
+                break;
+            }
+
+            for apple in game.apples().filter(|apple| ! before.contains(apple)) {
+                prop_assert!(game.distance(game.snake_head_pos(), &apple) >= min_apple_distance as usize);
+            }
+
+            prop_assert_eq!(game.apple_count(), 3);
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn weights_adding_up_past_u32_max_still_pick_by_weight(
+        weights in prop::collection::vec(prop_oneof![Just(0), Just(u32::MAX), any::<u32>()], 5),
+        seed: u64
+    ) {
+        let mut apple_weights = AppleWeights::default();
+
+        for (kind, weight) in AppleKind::ALL.into_iter().zip(weights) {
+            *apple_weights.get_mut(kind) = weight;
+        }
+
+        let all_zero = AppleKind::ALL.iter().all(|kind| apple_weights.get(*kind) == 0);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        for _ in 0..100 {
+            let kind = apple_weights.choose(&mut rng);
+
+            prop_assert!(apple_weights.get(kind) > 0 || (all_zero && kind == AppleKind::Normal));
+        }
+    }
+}
+
+#[test]
+fn equal_large_weights_pick_every_kind() {
+    let mut weights = AppleWeights::default();
+
+    for kind in AppleKind::ALL {
+        *weights.get_mut(kind) = u32::MAX;
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let picked: Vec<AppleKind> = (0..1000).map(|_| weights.choose(&mut rng)).collect();
+
+    for kind in AppleKind::ALL {
+        assert!(picked.contains(&kind), "{:?} never picked", kind);
+    }
+}
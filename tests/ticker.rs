@@ -0,0 +1,200 @@
+use std::{
+    cell::Cell,
+    time::Duration
+};
+
+use terminal_snake::{
+    ticker::{
+        Clock,
+        Ticker
+    },
+    Config,
+    Direction,
+    SnakeGame
+};
+
+// a clock that only moves when told to
+#[derive(Default)]
+struct ManualClock {
+    now: Cell<Duration>
+}
+
+impl ManualClock {
+    fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+// runs every tick that's due, like one frame of the game loop
+fn run_due<C: Clock>(ticker: &mut Ticker<C>, interval: Duration) -> u32 {
+    let mut ticks = 0;
+
+    while ticker.tick(interval) {
+        ticks += 1;
+    }
+
+    ticks
+}
+
+fn interval(steps_per_second: u32) -> Duration {
+    Duration::from_secs_f64(1.0 / steps_per_second as f64)
+}
+
+#[test]
+fn the_rate_stays_steady_when_frames_run_late() {
+    let clock = ManualClock::default();
+    let mut ticker = Ticker::new(&clock);
+    let interval = interval(10);
+
+    let mut ticks = run_due(&mut ticker, interval);
+
+    // frames of uneven length, none of them on time
+    for millis in [37, 130, 12, 99, 101, 160, 3, 58].iter().cycle().take(80) {
+        clock.advance(Duration::from_millis(*millis));
+        ticks += run_due(&mut ticker, interval);
+    }
+
+    // 6 seconds passed, a tick is due every 100 ms starting at 0
+    assert_eq!(clock.now(), Duration::from_millis(6000));
+    assert_eq!(ticks, 61);
+}
+
+#[test]
+fn more_than_a_thousand_steps_per_second_are_kept() {
+    let clock = ManualClock::default();
+    let mut ticker = Ticker::new(&clock);
+    let interval = interval(5000);
+
+    let mut ticks = run_due(&mut ticker, interval);
+
+    // input is only waited for in whole milliseconds, so several ticks run per frame
+    for _ in 0..1000 {
+        clock.advance(Duration::from_millis(1));
+
+        let due = run_due(&mut ticker, interval);
+
+        assert!((4..=6).contains(&due), "{} ticks in a millisecond", due);
+
+        ticks += due;
+    }
+
+    assert!((4999..=5001).contains(&ticks), "{} ticks in a second", ticks);
+}
+
+#[test]
+fn the_wait_for_input_ends_when_the_next_tick_is_due() {
+    let clock = ManualClock::default();
+    let mut ticker = Ticker::new(&clock);
+    let interval = interval(4);
+
+    assert_eq!(ticker.until_next(), Duration::ZERO);
+    assert_eq!(run_due(&mut ticker, interval), 1);
+
+    assert_eq!(ticker.until_next(), Duration::from_millis(250));
+
+    clock.advance(Duration::from_millis(100));
+    assert_eq!(ticker.until_next(), Duration::from_millis(150));
+    assert_eq!(run_due(&mut ticker, interval), 0);
+
+    clock.advance(Duration::from_millis(150));
+    assert_eq!(ticker.until_next(), Duration::ZERO);
+    assert_eq!(run_due(&mut ticker, interval), 1);
+}
+
+#[test]
+fn missed_ticks_are_skipped_after_a_stall() {
+    let clock = ManualClock::default();
+    let mut ticker = Ticker::new(&clock);
+    let interval = interval(10);
+
+    run_due(&mut ticker, interval);
+
+    // within the allowed lag the ticks are caught up on
+    clock.advance(Duration::from_millis(240));
+    assert_eq!(run_due(&mut ticker, interval), 2);
+
+    // far behind only one tick runs and the rate starts over from there
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(run_due(&mut ticker, interval), 1);
+
+    clock.advance(Duration::from_millis(99));
+    assert_eq!(run_due(&mut ticker, interval), 0);
+
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(run_due(&mut ticker, interval), 1);
+}
+
+#[test]
+fn nothing_is_caught_up_after_a_pause() {
+    let clock = ManualClock::default();
+    let mut ticker = Ticker::new(&clock);
+    let interval = interval(10);
+
+    run_due(&mut ticker, interval);
+
+    clock.advance(Duration::from_millis(200));
+    ticker.resync(interval);
+
+    assert_eq!(run_due(&mut ticker, interval), 0);
+    assert_eq!(ticker.until_next(), interval);
+}
+
+#[test]
+fn the_interval_can_change_between_ticks() {
+    let clock = ManualClock::default();
+    let mut ticker = Ticker::new(&clock);
+
+    let mut game = SnakeGame::from_config(&Config::default());
+    let mut ticked_at = vec![];
+
+    // every other tick twice as fast, like during a speed apple
+    for _ in 0..30 {
+        let interval = if ticked_at.len() % 2 == 0 { interval(10) } else { interval(20) };
+
+        if ticker.tick(interval) {
+            game.tick(&Direction::Right);
+            ticked_at.push(clock.now().as_millis());
+        }
+
+        clock.advance(Duration::from_millis(10));
+    }
+
+    assert_eq!(ticked_at, [0, 100, 150, 250]);
+    assert_eq!(game.ticks(), 4);
+}
+
+#[test]
+fn ticks_slower_than_the_interval_still_give_the_caller_a_break() {
+    for steps_per_second in [10, 100, 1000, 5000] {
+        let clock = ManualClock::default();
+        let mut ticker = Ticker::new(&clock);
+        let interval = interval(steps_per_second);
+
+        // every tick takes a millisecond longer than it may
+        let tick_time = interval + Duration::from_millis(1);
+        let most = (Ticker::<&ManualClock>::MAX_BURST.as_nanos() / tick_time.as_nanos()) as u32 + 1;
+
+        for _ in 0..100 {
+            let mut ticks = 0;
+
+            while ticker.tick(interval) {
+                clock.advance(tick_time);
+                ticks += 1;
+
+                assert!(ticks <= most, "{} ticks without a break at {}/s", ticks, steps_per_second);
+            }
+
+            assert!(ticks >= 1);
+            assert_eq!(ticker.until_next(), interval);
+
+            // the front-end waits for input until the next tick is due
+            clock.advance(ticker.until_next());
+        }
+    }
+}